@@ -0,0 +1,214 @@
+/*
+ * riichi_calc.h - C interface to the riichi-mahjong-calculator scoring engine
+ *
+ * The crate itself is an rlib only; build the shared library with the `ffi`
+ * feature:
+ *   cargo rustc --release --lib --no-default-features --features ffi --crate-type cdylib
+ * and link against target/release/libriichi_mahjong_calculator.{so,dylib}
+ * (riichi_mahjong_calculator.dll on Windows).
+ *
+ * Handles are opaque and owned by the caller. Every RcInput must be released
+ * with rc_input_free and every RcResult with rc_result_free. Functions that
+ * can fail return an RcStatus; rc_last_error() describes the last failure on
+ * the calling thread.
+ */
+#ifndef RIICHI_CALC_H
+#define RIICHI_CALC_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Version of this interface; compare with rc_api_version() at runtime.
+ * 2: RcOptions starts with struct_size and ends with kita */
+#define RC_API_VERSION 2
+
+/* Status codes, mirroring the engine's failure cases */
+typedef enum RcStatus {
+    RC_OK = 0,
+    RC_NULL_ARGUMENT = 1,
+    RC_INVALID_UTF8 = 2,
+    RC_PARSE_ERROR = 3,         /* MPSZ notation or JSON */
+    RC_INVALID_ARGUMENT = 4,    /* e.g. seat out of range */
+    RC_INVALID_RULES = 5,       /* unknown preset or invalid rule file */
+    RC_MISSING_WINNING_TILE = 10,
+    RC_TOO_MANY_COPIES = 11,
+    RC_TOO_MANY_MELDS = 12,
+    RC_WRONG_TILE_COUNT = 13,
+    RC_INVALID_CHII_START = 14,
+    RC_TOO_MANY_AKADORA = 15,
+    RC_INVALID_CONTEXT = 16,    /* flag set without its prerequisite */
+    RC_CONTEXT_MISMATCH = 17,   /* menzen/dealer flags disagree with the hand */
+    RC_INCOMPLETE_HAND = 18,
+    RC_NO_YAKU = 19,
+    RC_RESTRICTED_BY_RULES = 20,
+    RC_NOT_IN_TILE_SET = 21,    /* 2m-8m under three-player rules */
+    RC_INTERNAL_ERROR = 99
+} RcStatus;
+
+/* Seats and winds */
+enum {
+    RC_EAST = 0,
+    RC_SOUTH = 1,
+    RC_WEST = 2,
+    RC_NORTH = 3
+};
+
+/* RcOptions.flags */
+#define RC_FLAG_RIICHI        (1u << 0)
+#define RC_FLAG_DOUBLE_RIICHI (1u << 1)
+#define RC_FLAG_IPPATSU       (1u << 2)
+#define RC_FLAG_TENHOU        (1u << 3)
+#define RC_FLAG_CHIIHOU       (1u << 4)
+#define RC_FLAG_RENHOU        (1u << 5)
+#define RC_FLAG_HAITEI        (1u << 6)
+#define RC_FLAG_HOUTEI        (1u << 7)
+#define RC_FLAG_RINSHAN       (1u << 8)
+#define RC_FLAG_CHANKAN       (1u << 9)
+#define RC_FLAG_OPEN_RIICHI   (1u << 10)
+#define RC_FLAG_DISCARDER_RIICHI (1u << 11)
+
+/* Game context for rc_input_from_mpsz. Set struct_size to sizeof(RcOptions);
+ * any other value is rejected with RC_INVALID_ARGUMENT. */
+typedef struct RcOptions {
+    uint32_t struct_size; /* sizeof(RcOptions) */
+    uint8_t seat;         /* winner's seat wind */
+    uint8_t round;        /* round wind */
+    uint8_t dealer;       /* dealer's seat wind */
+    int8_t ron_from;      /* discarder's seat, or -1 for Tsumo */
+    uint8_t honba;
+    uint8_t kyoutaku;     /* riichi sticks on the table */
+    uint32_t flags;       /* RC_FLAG_* */
+    const char *dora;     /* indicators in MPSZ, e.g. "4s1z"; may be NULL */
+    const char *ura_dora; /* may be NULL */
+    uint8_t kita;         /* North tiles set aside (three-player rules) */
+} RcOptions;
+
+/* Hand limits returned by rc_result_limit */
+enum {
+    RC_LIMIT_NONE = 0,
+    RC_LIMIT_MANGAN = 1,
+    RC_LIMIT_HANEMAN = 2,
+    RC_LIMIT_BAIMAN = 3,
+    RC_LIMIT_SANBAIMAN = 4,
+    RC_LIMIT_YAKUMAN = 5
+};
+
+/* Yaku codes returned by rc_result_yaku */
+typedef enum RcYaku {
+    RC_YAKU_RIICHI = 0,
+    RC_YAKU_IPPATSU = 1,
+    RC_YAKU_MENZEN_TSUMO = 2,
+    RC_YAKU_PINFU = 3,
+    RC_YAKU_IIPEIKOU = 4,
+    RC_YAKU_HAITEI_RAOYUE = 5,
+    RC_YAKU_HOUTEI_RAOYUI = 6,
+    RC_YAKU_RINSHAN_KAIHOU = 7,
+    RC_YAKU_CHANKAN = 8,
+    RC_YAKU_TANYAO = 9,
+    RC_YAKU_YAKUHAI_JIKAZE = 10,
+    RC_YAKU_YAKUHAI_BAKAZE = 11,
+    RC_YAKU_YAKUHAI_HAKU = 12,
+    RC_YAKU_YAKUHAI_HATSU = 13,
+    RC_YAKU_YAKUHAI_CHUN = 14,
+    RC_YAKU_DABURU_RIICHI = 15,
+    RC_YAKU_CHIITOITSU = 16,
+    RC_YAKU_SANSHOKU_DOUJUN = 17,
+    RC_YAKU_ITTSU = 18,
+    RC_YAKU_CHANTA = 19,
+    RC_YAKU_TOITOI = 20,
+    RC_YAKU_SANANKOU = 21,
+    RC_YAKU_SANSHOKU_DOUKOU = 22,
+    RC_YAKU_SANKANTSU = 23,
+    RC_YAKU_SHOUSANGEN = 24,
+    RC_YAKU_HONROUTOU = 25,
+    RC_YAKU_RYANPEIKOU = 26,
+    RC_YAKU_JUNCHAN = 27,
+    RC_YAKU_HONITSU = 28,
+    RC_YAKU_CHINITSU = 29,
+    RC_YAKU_TENHOU = 30,
+    RC_YAKU_CHIIHOU = 31,
+    RC_YAKU_RENHOU = 32,
+    RC_YAKU_DAISANGEN = 33,
+    RC_YAKU_SUUANKOU = 34,
+    RC_YAKU_DAISUUSHI = 35,
+    RC_YAKU_SHOUSUUSHI = 36,
+    RC_YAKU_TSUUIISOU = 37,
+    RC_YAKU_CHINROUTOU = 38,
+    RC_YAKU_RYUUIISOU = 39,
+    RC_YAKU_SUUKANTSU = 40,
+    RC_YAKU_KOKUSHI_MUSOU = 41,
+    RC_YAKU_CHUUREN_POUTOU = 42,
+    RC_YAKU_SUUANKOU_TANKI = 43,
+    RC_YAKU_KOKUSHI_MUSOU_JUSANMEN = 44,
+    RC_YAKU_JUNSEI_CHUUREN_POUTOU = 45,
+    RC_YAKU_DORA = 46,
+    RC_YAKU_URA_DORA = 47,
+    RC_YAKU_AKA_DORA = 48,
+    RC_YAKU_OPEN_RIICHI = 49,
+    RC_YAKU_OPEN_RIICHI_DEAL_IN = 50,
+    RC_YAKU_NUKI_DORA = 51,
+} RcYaku;
+
+typedef struct RcInput RcInput;
+typedef struct RcResult RcResult;
+typedef struct RcRules RcRules;
+
+/* Inputs */
+
+/* Hand in MPSZ notation ("234m567p22s67s 345s+ 5s", winning tile last).
+ * options may be NULL for an East-seat Tsumo with no other context. */
+RcStatus rc_input_from_mpsz(const char *hand, const RcOptions *options, RcInput **out);
+/* Serialized UserInput, as produced by the serde feature */
+RcStatus rc_input_from_json(const char *json, RcInput **out);
+void rc_input_free(RcInput *input);
+
+/* Rules */
+
+/* Bundled preset: "standard", "tenhou", "majsoul", "wrc", "ema" or "sanma" */
+RcStatus rc_rules_preset(const char *name, RcRules **out);
+/* TOML rule file contents; RC_INVALID_RULES with the reason on failure */
+RcStatus rc_rules_from_toml(const char *toml, RcRules **out);
+void rc_rules_free(RcRules *rules);
+
+/* Scoring */
+
+RcStatus rc_calculate_agari(const RcInput *input, RcResult **out);
+/* rules may be NULL for the default rules */
+RcStatus rc_calculate_agari_with_rules(const RcInput *input, const RcRules *rules, RcResult **out);
+void rc_result_free(RcResult *result);
+
+/* Result getters (0, or -1 for rc_result_yaku, on a NULL handle) */
+
+uint32_t rc_result_han(const RcResult *result);
+uint32_t rc_result_fu(const RcResult *result);
+uint32_t rc_result_total_payment(const RcResult *result);
+uint32_t rc_result_oya_payment(const RcResult *result);
+uint32_t rc_result_ko_payment(const RcResult *result);
+uint32_t rc_result_limit(const RcResult *result);
+/* Points gained (+) or paid (-) by a seat, including honba and sticks;
+   0 for every seat after a Ron with no discarder */
+int32_t rc_result_point_delta(const RcResult *result, uint8_t seat);
+uint32_t rc_result_yaku_count(const RcResult *result);
+/* RcYaku at index (dora entries repeat), -1 when out of range */
+int32_t rc_result_yaku(const RcResult *result, uint32_t index);
+/* Serialized AgariResult; release with rc_string_free */
+char *rc_result_to_json(const RcResult *result);
+void rc_string_free(char *text);
+
+/* Names and errors (static or thread-local storage; do not free) */
+
+/* RC_API_VERSION the library was built with */
+uint32_t rc_api_version(void);
+
+const char *rc_yaku_name(int32_t code);
+/* Message of the last failure on this thread; valid until the next call */
+const char *rc_last_error(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* RIICHI_CALC_H */
@@ -0,0 +1,130 @@
+use crate::{EXIT_INVALID_HAND, report};
+use clap::Args;
+use rayon::prelude::*;
+use riichi_mahjong_calculator::{AgariResult, RuleSet, UserInput, calculate_agari};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct BatchArgs {
+    /// JSON Lines file with one UserInput per line (stdin if omitted or "-")
+    pub file: Option<PathBuf>,
+}
+
+// a scored line: the input line number, then the result's fields
+#[derive(Serialize)]
+struct NumberedResult<'a> {
+    line: usize,
+    #[serde(flatten)]
+    result: &'a AgariResult,
+}
+
+// outcome of one input line
+enum Outcome {
+    Scored(Box<AgariResult>),
+    Failed { kind: &'static str, message: String },
+}
+
+pub fn run(args: &BatchArgs, rules: &RuleSet) -> ExitCode {
+    let lines = match read_lines(args.file.as_ref()) {
+        Ok(lines) => lines,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::from(EXIT_INVALID_HAND);
+        }
+    };
+
+    // (line number, text), blank lines skipped
+    let entries: Vec<(usize, &str)> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .collect();
+
+    // par_iter keeps input order on collect
+    let outcomes: Vec<Outcome> = entries
+        .par_iter()
+        .map(|(_, line)| score_line(line, rules))
+        .collect();
+
+    let mut failures: Vec<(usize, &'static str)> = Vec::new();
+    let mut out = BufWriter::new(io::stdout().lock());
+    for ((line_no, _), outcome) in entries.iter().zip(&outcomes) {
+        let json = match outcome {
+            Outcome::Scored(result) => serde_json::to_string(&NumberedResult {
+                line: *line_no,
+                result,
+            })
+            .expect("AgariResult serializes"),
+            Outcome::Failed { kind, message } => {
+                failures.push((*line_no, kind));
+                serde_json::json!({ "error": kind, "message": message, "line": line_no })
+                    .to_string()
+            }
+        };
+        if writeln!(out, "{}", json).is_err() {
+            // stdout closed (e.g. piped into head)
+            return ExitCode::SUCCESS;
+        }
+    }
+    let _ = out.flush();
+
+    print_summary(entries.len(), &failures);
+    if failures.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(EXIT_INVALID_HAND)
+    }
+}
+
+pub fn read_lines(file: Option<&PathBuf>) -> io::Result<Vec<String>> {
+    match file {
+        Some(path) if path.as_os_str() != "-" => {
+            BufReader::new(File::open(path)?).lines().collect()
+        }
+        _ => io::stdin().lock().lines().collect(),
+    }
+}
+
+fn score_line(line: &str, rules: &RuleSet) -> Outcome {
+    let input: UserInput = match serde_json::from_str(line) {
+        Ok(input) => input,
+        Err(err) => {
+            return Outcome::Failed {
+                kind: "invalid_json",
+                message: err.to_string(),
+            };
+        }
+    };
+    match calculate_agari(&input, rules) {
+        Ok(result) => Outcome::Scored(Box::new(result)),
+        Err(err) => Outcome::Failed {
+            kind: report::error_kind(&err),
+            message: err.to_string(),
+        },
+    }
+}
+
+// summary on stderr so stdout stays one JSON object per line
+fn print_summary(total: usize, failures: &[(usize, &'static str)]) {
+    eprintln!(
+        "scored {} of {} hands, {} failed",
+        total - failures.len(),
+        total,
+        failures.len()
+    );
+
+    let mut by_kind: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (line_no, kind) in failures {
+        by_kind.entry(kind).or_default().push(*line_no);
+    }
+    for (kind, line_nos) in by_kind {
+        let list: Vec<String> = line_nos.iter().map(|n| n.to_string()).collect();
+        eprintln!("  {} ({}): lines {}", kind, line_nos.len(), list.join(", "));
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{EXIT_INVALID_HAND, batch, report};
+use clap::Args;
+use riichi_mahjong_calculator::implements::replay::tenhou6;
+use riichi_mahjong_calculator::{RuleSet, UserInput, calculate_agari};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct ExportArgs {
+    /// JSON Lines file with one UserInput per line (stdin if omitted or "-")
+    pub file: Option<PathBuf>,
+}
+
+// score every hand and print them as one tenhou.net/6 log
+pub fn run(args: &ExportArgs, rules: &RuleSet) -> ExitCode {
+    let lines = match batch::read_lines(args.file.as_ref()) {
+        Ok(lines) => lines,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::from(EXIT_INVALID_HAND);
+        }
+    };
+
+    let mut hands = Vec::new();
+    let mut failed = 0;
+    for (i, line) in lines.iter().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let scored = serde_json::from_str::<UserInput>(line)
+            .map_err(|err| ("invalid_json", err.to_string()))
+            .and_then(|input| match calculate_agari(&input, rules) {
+                Ok(result) => Ok((input, result)),
+                Err(err) => Err((report::error_kind(&err), err.to_string())),
+            });
+        match scored {
+            Ok(hand) => hands.push(hand),
+            Err((kind, message)) => {
+                eprintln!("line {}: {}: {}", i + 1, kind, message);
+                failed += 1;
+            }
+        }
+    }
+
+    println!("{}", tenhou6::export_log(&hands, rules));
+    if failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(EXIT_INVALID_HAND)
+    }
+}
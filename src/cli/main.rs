@@ -0,0 +1,71 @@
+// riichi-calc: command-line frontend to the scoring engine
+mod batch;
+mod export;
+mod report;
+mod rules;
+mod score;
+mod serve;
+mod verify;
+
+use clap::{Parser, Subcommand};
+use std::process::ExitCode;
+
+// Exit codes (2 is clap's usage error, also used for unusable rules)
+pub const EXIT_INVALID_HAND: u8 = 1;
+pub const EXIT_USAGE: u8 = 2;
+pub const EXIT_NO_YAKU: u8 = 3;
+
+#[derive(Parser)]
+#[command(name = "riichi-calc", version, about = "Riichi Mahjong hand scorer")]
+#[command(
+    after_help = "Exit codes: 0 scored, 1 invalid hand, 2 usage error, 3 no yaku (or too few under the rules)"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Rule preset (standard, tenhou, majsoul, wrc, ema, sanma) or TOML rule file
+    #[arg(long, global = true, value_name = "PRESET|FILE")]
+    rules: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Score a single hand written in MPSZ notation
+    Score(score::ScoreArgs),
+    /// Score one UserInput JSON per line, in parallel
+    Batch(batch::BatchArgs),
+    /// Serve the engine over HTTP/JSON
+    Serve(serve::ServeArgs),
+    /// Check the engine against the scores recorded in game logs
+    Verify(verify::VerifyArgs),
+    /// Write scored UserInput JSON lines as a tenhou.net/6 log
+    Export(export::ExportArgs),
+    /// Print the rules in effect as a TOML rule file
+    Rules,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let rules = match rules::load(cli.rules.as_deref()) {
+        Ok(rules) => rules,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+    match cli.command {
+        Command::Score(args) => score::run(&args, &rules),
+        Command::Batch(args) => batch::run(&args, &rules),
+        Command::Serve(args) => serve::run(&args, &rules),
+        // without --rules, logs are scored under their own site's rules
+        Command::Verify(args) => match cli.rules {
+            Some(_) => verify::run(&args, &rules),
+            None => verify::run(&args, &args.format.default_rules()),
+        },
+        Command::Export(args) => export::run(&args, &rules),
+        Command::Rules => {
+            print!("{}", rules.to_toml());
+            ExitCode::SUCCESS
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{EXIT_INVALID_HAND, EXIT_NO_YAKU};
+use riichi_mahjong_calculator::{
+    AgariError, AgariResult, AgariType, HandLimit, Kaze, UserInput, Yaku,
+};
+use std::process::ExitCode;
+
+const SEATS: [(Kaze, &str); 4] = [
+    (Kaze::Ton, "East"),
+    (Kaze::Nan, "South"),
+    (Kaze::Shaa, "West"),
+    (Kaze::Pei, "North"),
+];
+
+pub fn scored(input: &UserInput, result: &AgariResult, sanma: bool, json: bool) -> ExitCode {
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string(result).expect("AgariResult serializes")
+        );
+    } else {
+        print!("{}", describe(input, result, sanma));
+    }
+    ExitCode::SUCCESS
+}
+
+pub fn agari_error(err: &AgariError, json: bool) -> ExitCode {
+    let code = match err {
+        AgariError::NoYaku { .. } | AgariError::RestrictedByRules { .. } => EXIT_NO_YAKU,
+        _ => EXIT_INVALID_HAND,
+    };
+    failure(error_kind(err), &err.to_string(), json, code)
+}
+
+// "no_yaku" for complete hands without yaku, "restricted_by_rules" for
+// hands the rules do not allow, "invalid_hand" otherwise
+pub fn error_kind(err: &AgariError) -> &'static str {
+    match err {
+        AgariError::NoYaku { .. } => "no_yaku",
+        AgariError::RestrictedByRules { .. } => "restricted_by_rules",
+        _ => "invalid_hand",
+    }
+}
+
+pub fn invalid_hand(message: &str, json: bool) -> ExitCode {
+    failure("invalid_hand", message, json, EXIT_INVALID_HAND)
+}
+
+// JSON errors go to stdout so scripts read one stream
+fn failure(kind: &str, message: &str, json: bool, code: u8) -> ExitCode {
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({ "error": kind, "message": message })
+        );
+    } else {
+        eprintln!("error: {}", message);
+    }
+    ExitCode::from(code)
+}
+
+// human-readable summary; a three-player table has no North seat
+pub fn describe(input: &UserInput, result: &AgariResult, sanma: bool) -> String {
+    let mut out = format!("{}\n\n", input);
+
+    let mut dora = 0;
+    let mut ura_dora = 0;
+    for yaku in &result.yaku_list {
+        match yaku {
+            Yaku::Dora => dora += 1,
+            Yaku::UraDora => ura_dora += 1,
+            Yaku::AkaDora | Yaku::NukiDora => {}
+            _ => out.push_str(&format!("  {}\n", yaku)),
+        }
+    }
+    if dora > 0 {
+        out.push_str(&format!("  Dora x{}\n", dora));
+    }
+    if ura_dora > 0 {
+        out.push_str(&format!("  Ura Dora x{}\n", ura_dora));
+    }
+    if result.num_akadora > 0 {
+        out.push_str(&format!("  Red Dora x{}\n", result.num_akadora));
+    }
+    if result.num_nukidora > 0 {
+        out.push_str(&format!("  Nuki Dora x{}\n", result.num_nukidora));
+    }
+
+    let han_fu = if result.limit_name == Some(HandLimit::Yakuman) {
+        format!("{} han", result.han)
+    } else {
+        format!("{} han {} fu", result.han, result.fu)
+    };
+    match &result.limit_name {
+        Some(limit) => out.push_str(&format!("\n{} ({})\n", han_fu, limit_label(limit))),
+        None => out.push_str(&format!("\n{}\n", han_fu)),
+    }
+
+    let payment = match (result.agari_type, result.is_oya) {
+        (AgariType::Tsumo, true) => format!("{} all", result.oya_payment),
+        (AgariType::Tsumo, false) => format!("{}/{}", result.ko_payment, result.oya_payment),
+        (AgariType::Ron, _) => result.total_payment.to_string(),
+    };
+    out.push_str(&format!(
+        "{}: {} ({} points",
+        agari_label(result.agari_type),
+        payment,
+        result.total_payment
+    ));
+    if result.honba > 0 {
+        out.push_str(&format!(", {} honba", result.honba));
+    }
+    out.push_str(")\n");
+
+    // the exchange is unknown for a Ron without a discarder
+    let seats = if sanma { 3 } else { 4 };
+    let deltas: Option<Vec<String>> = SEATS
+        .iter()
+        .take(seats)
+        .map(|(seat, name)| Some(format!("{} {:+}", name, result.point_delta(*seat)?)))
+        .collect();
+    if let Some(deltas) = deltas {
+        out.push_str(&format!("{}\n", deltas.join("  ")));
+    }
+    out
+}
+
+fn agari_label(agari_type: AgariType) -> &'static str {
+    match agari_type {
+        AgariType::Tsumo => "Tsumo",
+        AgariType::Ron => "Ron",
+    }
+}
+
+fn limit_label(limit: &HandLimit) -> &'static str {
+    match limit {
+        HandLimit::Mangan => "Mangan",
+        HandLimit::Haneman => "Haneman",
+        HandLimit::Baiman => "Baiman",
+        HandLimit::Sanbaiman => "Sanbaiman",
+        HandLimit::Yakuman => "Yakuman",
+    }
+}
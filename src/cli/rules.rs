@@ -0,0 +1,21 @@
+use riichi_mahjong_calculator::RuleSet;
+use std::fs;
+
+// --rules: a preset name or the path of a TOML rule file
+pub fn load(spec: Option<&str>) -> Result<RuleSet, String> {
+    let Some(spec) = spec else {
+        return Ok(RuleSet::default());
+    };
+    if let Some(rules) = RuleSet::preset(spec) {
+        return Ok(rules);
+    }
+    let text = fs::read_to_string(spec).map_err(|err| {
+        format!(
+            "'{}' is not a preset ({}) or a readable rule file: {}",
+            spec,
+            RuleSet::PRESETS.join(", "),
+            err
+        )
+    })?;
+    RuleSet::from_toml(&text).map_err(|err| format!("{}: {}", spec, err))
+}
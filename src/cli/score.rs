@@ -0,0 +1,156 @@
+use crate::report;
+use clap::{ArgGroup, Args};
+use riichi_mahjong_calculator::{Hai, Kaze, RuleSet, UserInput, calculate_agari, parse_hand};
+use std::process::ExitCode;
+
+#[derive(Args)]
+#[command(group(ArgGroup::new("win").required(true).args(["tsumo", "ron"])))]
+pub struct ScoreArgs {
+    /// Hand in MPSZ notation, winning tile last (e.g. "234m567p22s 345s+ 5s")
+    pub hand: String,
+
+    /// Win by self-draw
+    #[arg(long)]
+    pub tsumo: bool,
+    /// Win off the discard of this seat
+    #[arg(long, value_name = "SEAT", value_parser = parse_seat)]
+    pub ron: Option<Kaze>,
+
+    /// Seat wind of the winner
+    #[arg(long, value_name = "SEAT", value_parser = parse_seat, default_value = "east")]
+    pub seat: Kaze,
+    /// Round wind
+    #[arg(long, value_name = "SEAT", value_parser = parse_seat, default_value = "east")]
+    pub round: Kaze,
+    /// Seat wind of the dealer
+    #[arg(long, value_name = "SEAT", value_parser = parse_seat, default_value = "east")]
+    pub dealer: Kaze,
+    #[arg(long, default_value_t = 0)]
+    pub honba: u8,
+    /// Riichi sticks on the table
+    #[arg(long, default_value_t = 0)]
+    pub kyoutaku: u8,
+    /// North tiles set aside as nukidora (three-player rules)
+    #[arg(long, default_value_t = 0)]
+    pub kita: u8,
+
+    /// Dora indicator (repeatable or comma separated)
+    #[arg(long, value_name = "TILE", value_delimiter = ',')]
+    pub dora: Vec<Hai>,
+    /// Ura dora indicator (repeatable or comma separated)
+    #[arg(long, value_name = "TILE", value_delimiter = ',')]
+    pub ura: Vec<Hai>,
+
+    #[arg(long)]
+    pub riichi: bool,
+    #[arg(long)]
+    pub double_riichi: bool,
+    #[arg(long)]
+    pub open_riichi: bool,
+    #[arg(long)]
+    pub ippatsu: bool,
+    #[arg(long)]
+    pub tenhou: bool,
+    #[arg(long)]
+    pub chiihou: bool,
+    #[arg(long)]
+    pub renhou: bool,
+    #[arg(long)]
+    pub haitei: bool,
+    #[arg(long)]
+    pub houtei: bool,
+    #[arg(long)]
+    pub rinshan: bool,
+    #[arg(long)]
+    pub chankan: bool,
+    /// The seat that dealt in was in riichi
+    #[arg(long)]
+    pub discarder_riichi: bool,
+
+    /// Print the result as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+pub fn run(args: &ScoreArgs, rules: &RuleSet) -> ExitCode {
+    let input = match build_input(args) {
+        Ok(input) => input,
+        Err(message) => return report::invalid_hand(&message, args.json),
+    };
+
+    match calculate_agari(&input, rules) {
+        Ok(result) => report::scored(&input, &result, rules.sanma, args.json),
+        Err(err) => report::agari_error(&err, args.json),
+    }
+}
+
+fn build_input(args: &ScoreArgs) -> Result<UserInput, String> {
+    let mut builder = parse_hand(&args.hand)
+        .map_err(|err| err.to_string())?
+        .seat(args.seat)
+        .round(args.round)
+        .dealer(args.dealer)
+        .honba(args.honba)
+        .kyoutaku(args.kyoutaku)
+        .kita(args.kita)
+        .dora(args.dora.iter().copied())
+        .ura_dora(args.ura.iter().copied());
+
+    builder = match args.ron {
+        Some(discarder) => builder.ron(discarder),
+        None => builder.tsumo(),
+    };
+
+    if args.riichi {
+        builder = builder.riichi();
+    }
+    if args.double_riichi {
+        builder = builder.double_riichi();
+    }
+    if args.open_riichi {
+        builder = builder.open_riichi();
+    }
+    if args.ippatsu {
+        builder = builder.ippatsu();
+    }
+    if args.tenhou {
+        builder = builder.tenhou();
+    }
+    if args.chiihou {
+        builder = builder.chiihou();
+    }
+    if args.renhou {
+        builder = builder.renhou();
+    }
+    if args.haitei {
+        builder = builder.haitei();
+    }
+    if args.houtei {
+        builder = builder.houtei();
+    }
+    if args.rinshan {
+        builder = builder.rinshan();
+    }
+    if args.chankan {
+        builder = builder.chankan();
+    }
+    if args.discarder_riichi {
+        builder = builder.discarder_in_riichi();
+    }
+
+    builder.build().map_err(|err| err.to_string())
+}
+
+// east/south/west/north or their initials
+pub fn parse_seat(text: &str) -> Result<Kaze, String> {
+    match text.to_ascii_lowercase().as_str() {
+        "east" | "e" => Ok(Kaze::Ton),
+        "south" | "s" => Ok(Kaze::Nan),
+        "west" | "w" => Ok(Kaze::Shaa),
+        "north" | "n" => Ok(Kaze::Pei),
+        _ => Err(format!(
+            "'{}' is not a seat (east, south, west, north)",
+            text
+        )),
+    }
+}
@@ -0,0 +1,131 @@
+use crate::report;
+use clap::Args;
+use riichi_mahjong_calculator::implements::score_calculator::{
+    han::get_han_value, yakuman::count_yakuman,
+};
+use riichi_mahjong_calculator::{
+    AgariError, RuleSet, UserInput, Yaku, calculate_agari, calculate_agari_all,
+};
+use serde_json::{Value, json};
+use std::io::Read;
+use std::process::ExitCode;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+// request bodies larger than this are refused
+const MAX_BODY_BYTES: u64 = 64 * 1024;
+
+#[derive(Args)]
+pub struct ServeArgs {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    pub addr: String,
+}
+
+pub fn run(args: &ServeArgs, rules: &RuleSet) -> ExitCode {
+    let server = match Server::http(&args.addr) {
+        Ok(server) => server,
+        Err(err) => {
+            eprintln!("error: cannot listen on {}: {}", args.addr, err);
+            return ExitCode::FAILURE;
+        }
+    };
+    eprintln!("listening on http://{}", args.addr);
+
+    for mut request in server.incoming_requests() {
+        let (status, body) = route(&mut request, rules);
+        let response = Response::from_string(body)
+            .with_status_code(status)
+            .with_header(
+                Header::from_bytes("Content-Type", "application/json")
+                    .expect("static header is valid"),
+            );
+        if let Err(err) = request.respond(response) {
+            eprintln!("error: failed to respond: {}", err);
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+// (status code, JSON body)
+fn route(request: &mut Request, rules: &RuleSet) -> (u16, String) {
+    let path = request.url().split('?').next().unwrap_or("").to_string();
+
+    match (request.method(), path.as_str()) {
+        (Method::Post, "/score") => match read_input(request) {
+            Ok(input) => respond_with(calculate_agari(&input, rules)),
+            Err(error) => error,
+        },
+        (Method::Post, "/score/all") => match read_input(request) {
+            Ok(input) => respond_with(calculate_agari_all(&input, rules)),
+            Err(error) => error,
+        },
+        (Method::Get, "/yaku") => (200, yaku_table(rules).to_string()),
+        (Method::Get, "/rules") => (200, json!(rules).to_string()),
+        (_, "/score" | "/score/all" | "/yaku" | "/rules") => {
+            error_body(405, "method_not_allowed", "Method not allowed")
+        }
+        _ => error_body(404, "not_found", &format!("No endpoint at {}", path)),
+    }
+}
+
+fn read_input(request: &mut Request) -> Result<UserInput, (u16, String)> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_BYTES + 1)
+        .read_to_string(&mut body)
+        .map_err(|err| error_body(400, "invalid_json", &err.to_string()))?;
+    if body.len() as u64 > MAX_BODY_BYTES {
+        return Err(error_body(
+            413,
+            "too_large",
+            &format!("Request body exceeds {} bytes", MAX_BODY_BYTES),
+        ));
+    }
+    serde_json::from_str(&body).map_err(|err| error_body(400, "invalid_json", &err.to_string()))
+}
+
+fn respond_with<T: serde::Serialize>(result: Result<T, AgariError>) -> (u16, String) {
+    match result {
+        Ok(value) => (
+            200,
+            serde_json::to_string(&value).expect("result serializes"),
+        ),
+        Err(err) => error_body(422, report::error_kind(&err), &err.to_string()),
+    }
+}
+
+fn error_body(status: u16, kind: &str, message: &str) -> (u16, String) {
+    let body = json!({ "error": kind, "message": message });
+    (status, body.to_string())
+}
+
+// every yaku with its han value; yakuman count as multiples of 13 han
+fn yaku_table(rules: &RuleSet) -> Value {
+    let entries: Vec<Value> = Yaku::ALL
+        .iter()
+        .map(|yaku| {
+            let yakuman = count_yakuman(&[*yaku], rules);
+            let han_closed = if yakuman > 0 {
+                13 * yakuman
+            } else {
+                get_han_value(yaku, true, rules) as u32
+            };
+            let han_open = (!yaku.is_menzen_only()).then(|| {
+                if yakuman > 0 {
+                    han_closed
+                } else {
+                    get_han_value(yaku, false, rules) as u32
+                }
+            });
+            json!({
+                "id": yaku,
+                "name": yaku.to_string(),
+                "han_closed": han_closed,
+                "han_open": han_open,
+                "yakuman": yakuman,
+            })
+        })
+        .collect();
+    Value::Array(entries)
+}
@@ -0,0 +1,143 @@
+use clap::{Args, ValueEnum};
+use flate2::read::GzDecoder;
+use riichi_mahjong_calculator::implements::replay::{
+    Discrepancy, RecordedAgari, majsoul, mjai, tenhou, tenhou6, verify,
+};
+use riichi_mahjong_calculator::{ReplayError, RuleSet};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct VerifyArgs {
+    /// Game records to check (gzip-compressed files are accepted)
+    #[arg(required = true)]
+    pub files: Vec<PathBuf>,
+    /// Log format; without --rules, hands are scored under the site's preset
+    /// (tenhou for tenhou, tenhou6 and mjai; majsoul for majsoul)
+    #[arg(long, value_enum, default_value_t = Format::Tenhou)]
+    pub format: Format,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    /// Tenhou mjlog (XML)
+    Tenhou,
+    /// tenhou.net/6 viewer JSON
+    Tenhou6,
+    /// Mahjong Soul paipu (decoded JSON records)
+    Majsoul,
+    /// mjai event stream (JSON Lines)
+    Mjai,
+}
+
+impl Format {
+    // rules of the site the log comes from, used when --rules is not given
+    pub fn default_rules(self) -> RuleSet {
+        let preset = match self {
+            Format::Tenhou | Format::Tenhou6 | Format::Mjai => "tenhou",
+            Format::Majsoul => "majsoul",
+        };
+        RuleSet::preset(preset).expect("built-in preset")
+    }
+}
+
+// one win and where the engine disagrees with the log
+struct Checked {
+    round: String,
+    winner: usize,
+    discrepancies: Vec<Discrepancy>,
+}
+
+pub fn run(args: &VerifyArgs, rules: &RuleSet) -> ExitCode {
+    let mut checked = 0;
+    let mut mismatched = 0;
+    let mut failed_files = 0;
+
+    for path in &args.files {
+        let checked_list = match read_log(path).and_then(|text| check(&text, args.format, rules)) {
+            Ok(checked_list) => checked_list,
+            Err(err) => {
+                eprintln!("{}: error: {}", path.display(), err);
+                failed_files += 1;
+                continue;
+            }
+        };
+
+        for agari in &checked_list {
+            checked += 1;
+            if agari.discrepancies.is_empty() {
+                continue;
+            }
+            mismatched += 1;
+            for discrepancy in &agari.discrepancies {
+                println!(
+                    "{}: {} (player {}): {} expected {}, got {}",
+                    path.display(),
+                    agari.round,
+                    agari.winner,
+                    discrepancy.field,
+                    discrepancy.expected,
+                    discrepancy.actual
+                );
+            }
+        }
+    }
+
+    eprintln!(
+        "checked {} hands in {} files, {} disagree, {} files unreadable",
+        checked,
+        args.files.len(),
+        mismatched,
+        failed_files
+    );
+    if mismatched == 0 && failed_files == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn check(text: &str, format: Format, rules: &RuleSet) -> Result<Vec<Checked>, String> {
+    let imported: Result<Vec<RecordedAgari>, ReplayError> = match format {
+        Format::Tenhou => tenhou::parse_mjlog(text),
+        Format::Tenhou6 => tenhou6::parse_log(text)
+            .map(|rounds| rounds.into_iter().flat_map(|round| round.agari).collect()),
+        Format::Majsoul => majsoul::parse_paipu(text),
+        // hora events are scored as they are read
+        Format::Mjai => {
+            let hora_list = mjai::parse_events(text, rules).map_err(|err| err.to_string())?;
+            return Ok(hora_list
+                .into_iter()
+                .map(|hora| Checked {
+                    discrepancies: hora.discrepancies(),
+                    round: hora.round,
+                    winner: hora.actor,
+                })
+                .collect());
+        }
+    };
+    let agari_list = imported.map_err(|err| err.to_string())?;
+    Ok(agari_list
+        .into_iter()
+        .map(|agari| Checked {
+            discrepancies: verify(&agari, rules),
+            round: agari.round,
+            winner: agari.winner,
+        })
+        .collect())
+}
+
+// plain or gzip-compressed text
+fn read_log(path: &Path) -> Result<String, String> {
+    let bytes = fs::read(path).map_err(|err| err.to_string())?;
+    if !bytes.starts_with(&[0x1f, 0x8b]) {
+        return String::from_utf8(bytes).map_err(|err| err.to_string());
+    }
+    let mut text = String::new();
+    GzDecoder::new(bytes.as_slice())
+        .read_to_string(&mut text)
+        .map_err(|err| err.to_string())?;
+    Ok(text)
+}
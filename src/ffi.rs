@@ -0,0 +1,591 @@
+// C ABI over the scoring engine (see include/riichi_calc.h)
+//
+// Inputs and results are opaque heap handles owned by the caller and
+// released with rc_input_free / rc_result_free. Every fallible call returns
+// an RcStatus; the message of the last failure on the calling thread is
+// available from rc_last_error.
+
+use crate::implements::types::{
+    builder::UserInputBuilder,
+    error::AgariError,
+    input::UserInput,
+    scoring::{AgariResult, HandLimit},
+    tiles::{Hai, Kaze},
+    yaku::Yaku,
+};
+use crate::{RuleSet, calculate_agari, parse_hand};
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::ptr;
+use std::sync::OnceLock;
+
+// mirrors the failure cases of the engine; values are part of the ABI
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RcStatus {
+    Ok = 0,
+    NullArgument = 1,
+    InvalidUtf8 = 2,
+    ParseError = 3, // MPSZ notation or JSON
+    InvalidArgument = 4,
+    InvalidRules = 5, // unknown preset or rule file that fails validation
+    MissingWinningTile = 10,
+    TooManyCopies = 11,
+    TooManyMelds = 12,
+    WrongTileCount = 13,
+    InvalidChiiStart = 14,
+    TooManyAkadora = 15,
+    InvalidContext = 16,
+    ContextMismatch = 17,
+    IncompleteHand = 18,
+    NoYaku = 19,
+    RestrictedByRules = 20,
+    NotInTileSet = 21,
+    InternalError = 99,
+}
+
+impl From<&AgariError> for RcStatus {
+    fn from(err: &AgariError) -> Self {
+        match err {
+            AgariError::InvalidRules(_) => RcStatus::InvalidRules,
+            AgariError::MissingWinningTile => RcStatus::MissingWinningTile,
+            AgariError::TooManyCopies { .. } => RcStatus::TooManyCopies,
+            AgariError::TooManyMelds { .. } => RcStatus::TooManyMelds,
+            AgariError::WrongTileCount { .. } => RcStatus::WrongTileCount,
+            AgariError::InvalidChiiStart { .. } => RcStatus::InvalidChiiStart,
+            AgariError::TooManyAkadora { .. } => RcStatus::TooManyAkadora,
+            AgariError::InvalidContext { .. } => RcStatus::InvalidContext,
+            AgariError::ContextMismatch { .. } => RcStatus::ContextMismatch,
+            AgariError::IncompleteHand { .. } => RcStatus::IncompleteHand,
+            AgariError::NoYaku { .. } => RcStatus::NoYaku,
+            AgariError::RestrictedByRules { .. } => RcStatus::RestrictedByRules,
+            AgariError::NotInTileSet { .. } => RcStatus::NotInTileSet,
+        }
+    }
+}
+
+// RcOptions.flags
+pub const RC_FLAG_RIICHI: u32 = 1 << 0;
+pub const RC_FLAG_DOUBLE_RIICHI: u32 = 1 << 1;
+pub const RC_FLAG_IPPATSU: u32 = 1 << 2;
+pub const RC_FLAG_TENHOU: u32 = 1 << 3;
+pub const RC_FLAG_CHIIHOU: u32 = 1 << 4;
+pub const RC_FLAG_RENHOU: u32 = 1 << 5;
+pub const RC_FLAG_HAITEI: u32 = 1 << 6;
+pub const RC_FLAG_HOUTEI: u32 = 1 << 7;
+pub const RC_FLAG_RINSHAN: u32 = 1 << 8;
+pub const RC_FLAG_CHANKAN: u32 = 1 << 9;
+pub const RC_FLAG_OPEN_RIICHI: u32 = 1 << 10;
+pub const RC_FLAG_DISCARDER_RIICHI: u32 = 1 << 11;
+
+// version of the C interface, RC_API_VERSION in the header
+pub const RC_API_VERSION: u32 = 2;
+
+// game context for rc_input_from_mpsz; seats are 0 East .. 3 North
+// struct_size comes first so a caller built against another layout is
+// turned away before the rest is read
+#[repr(C)]
+pub struct RcOptions {
+    pub struct_size: u32, // sizeof(RcOptions) as the caller compiled it
+    pub seat: u8,
+    pub round: u8,
+    pub dealer: u8,
+    pub ron_from: i8, // discarder seat, or -1 for Tsumo
+    pub honba: u8,
+    pub kyoutaku: u8,
+    pub flags: u32,
+    pub dora: *const c_char,     // MPSZ indicators, may be NULL
+    pub ura_dora: *const c_char, // MPSZ indicators, may be NULL
+    pub kita: u8,                // North tiles set aside (three-player rules)
+}
+
+pub struct RcInput(UserInput);
+pub struct RcResult(AgariResult);
+pub struct RcRules(RuleSet);
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+fn fail(status: RcStatus, message: impl ToString) -> RcStatus {
+    let message = CString::new(message.to_string().replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+    status
+}
+
+// run body, turning panics into InternalError
+fn guarded(body: impl FnOnce() -> RcStatus) -> RcStatus {
+    catch_unwind(AssertUnwindSafe(body))
+        .unwrap_or_else(|_| fail(RcStatus::InternalError, "internal error in the scorer"))
+}
+
+unsafe fn read_str<'a>(text: *const c_char) -> Result<&'a str, RcStatus> {
+    if text.is_null() {
+        return Err(fail(RcStatus::NullArgument, "string argument is NULL"));
+    }
+    unsafe { CStr::from_ptr(text) }
+        .to_str()
+        .map_err(|err| fail(RcStatus::InvalidUtf8, err))
+}
+
+fn seat(index: u8) -> Result<Kaze, RcStatus> {
+    match index {
+        0 => Ok(Kaze::Ton),
+        1 => Ok(Kaze::Nan),
+        2 => Ok(Kaze::Shaa),
+        3 => Ok(Kaze::Pei),
+        _ => Err(fail(
+            RcStatus::InvalidArgument,
+            format!("seat {} is out of range (0-3)", index),
+        )),
+    }
+}
+
+// indicator tiles written as plain MPSZ, e.g. "4s1z"
+unsafe fn read_indicators(text: *const c_char) -> Result<Vec<Hai>, RcStatus> {
+    if text.is_null() {
+        return Ok(Vec::new());
+    }
+    let text = unsafe { read_str(text) }?;
+    let mut tiles = Vec::new();
+    let mut digits = Vec::new();
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        for digit in digits.drain(..) {
+            let tile: Hai = format!("{}{}", digit, c)
+                .parse()
+                .map_err(|err| fail(RcStatus::ParseError, err))?;
+            tiles.push(tile);
+        }
+    }
+    if !digits.is_empty() {
+        return Err(fail(
+            RcStatus::ParseError,
+            format!("'{}' has numbers without a suit", text),
+        ));
+    }
+    Ok(tiles)
+}
+
+// only struct_size is read until it matches this library's layout
+unsafe fn read_options<'a>(options: *const RcOptions) -> Result<Option<&'a RcOptions>, RcStatus> {
+    if options.is_null() {
+        return Ok(None);
+    }
+    let size = unsafe { ptr::read(options.cast::<u32>()) } as usize;
+    if size != size_of::<RcOptions>() {
+        return Err(fail(
+            RcStatus::InvalidArgument,
+            format!(
+                "RcOptions.struct_size is {}, expected {}; rebuild against riichi_calc.h version {}",
+                size,
+                size_of::<RcOptions>(),
+                RC_API_VERSION
+            ),
+        ));
+    }
+    Ok(unsafe { options.as_ref() })
+}
+
+type Setter = fn(UserInputBuilder) -> UserInputBuilder;
+
+unsafe fn apply_options(
+    builder: UserInputBuilder,
+    options: &RcOptions,
+) -> Result<UserInputBuilder, RcStatus> {
+    let mut builder = builder
+        .seat(seat(options.seat)?)
+        .round(seat(options.round)?)
+        .dealer(seat(options.dealer)?)
+        .honba(options.honba)
+        .kyoutaku(options.kyoutaku)
+        .kita(options.kita)
+        .dora(unsafe { read_indicators(options.dora) }?)
+        .ura_dora(unsafe { read_indicators(options.ura_dora) }?);
+    builder = match options.ron_from {
+        -1 => builder.tsumo(),
+        index => builder.ron(seat(index as u8)?),
+    };
+
+    let setters: [(u32, Setter); 12] = [
+        (RC_FLAG_RIICHI, UserInputBuilder::riichi),
+        (RC_FLAG_DOUBLE_RIICHI, UserInputBuilder::double_riichi),
+        (RC_FLAG_IPPATSU, UserInputBuilder::ippatsu),
+        (RC_FLAG_TENHOU, UserInputBuilder::tenhou),
+        (RC_FLAG_CHIIHOU, UserInputBuilder::chiihou),
+        (RC_FLAG_RENHOU, UserInputBuilder::renhou),
+        (RC_FLAG_HAITEI, UserInputBuilder::haitei),
+        (RC_FLAG_HOUTEI, UserInputBuilder::houtei),
+        (RC_FLAG_RINSHAN, UserInputBuilder::rinshan),
+        (RC_FLAG_CHANKAN, UserInputBuilder::chankan),
+        (RC_FLAG_OPEN_RIICHI, UserInputBuilder::open_riichi),
+        (
+            RC_FLAG_DISCARDER_RIICHI,
+            UserInputBuilder::discarder_in_riichi,
+        ),
+    ];
+    for (flag, set) in setters {
+        if options.flags & flag != 0 {
+            builder = set(builder);
+        }
+    }
+    Ok(builder)
+}
+
+fn store<T>(out: *mut *mut T, value: T) -> RcStatus {
+    unsafe { *out = Box::into_raw(Box::new(value)) };
+    RcStatus::Ok
+}
+
+// Inputs
+
+/// # Safety
+/// `hand` must be a NUL-terminated string, `options` NULL or a valid
+/// RcOptions, and `out` a valid pointer to write the handle to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_input_from_mpsz(
+    hand: *const c_char,
+    options: *const RcOptions,
+    out: *mut *mut RcInput,
+) -> RcStatus {
+    guarded(|| {
+        if out.is_null() {
+            return fail(RcStatus::NullArgument, "out is NULL");
+        }
+        unsafe { *out = ptr::null_mut() };
+        let hand = match unsafe { read_str(hand) } {
+            Ok(hand) => hand,
+            Err(status) => return status,
+        };
+        let mut builder = match parse_hand(hand) {
+            Ok(builder) => builder,
+            Err(err) => return fail(RcStatus::ParseError, err),
+        };
+
+        builder = match unsafe { read_options(options) } {
+            Ok(Some(options)) => match unsafe { apply_options(builder, options) } {
+                Ok(builder) => builder,
+                Err(status) => return status,
+            },
+            Ok(None) => builder.tsumo(),
+            Err(status) => return status,
+        };
+
+        match builder.build() {
+            Ok(input) => store(out, RcInput(input)),
+            Err(err) => fail(RcStatus::from(&err), err),
+        }
+    })
+}
+
+/// # Safety
+/// `json` must be a NUL-terminated string and `out` a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_input_from_json(
+    json: *const c_char,
+    out: *mut *mut RcInput,
+) -> RcStatus {
+    guarded(|| {
+        if out.is_null() {
+            return fail(RcStatus::NullArgument, "out is NULL");
+        }
+        unsafe { *out = ptr::null_mut() };
+        let json = match unsafe { read_str(json) } {
+            Ok(json) => json,
+            Err(status) => return status,
+        };
+        let input: UserInput = match serde_json::from_str(json) {
+            Ok(input) => input,
+            Err(err) => return fail(RcStatus::ParseError, err),
+        };
+        match input.validate() {
+            Ok(()) => store(out, RcInput(input)),
+            Err(err) => fail(RcStatus::from(&err), err),
+        }
+    })
+}
+
+/// # Safety
+/// `input` must be NULL or a handle from rc_input_from_*, freed only once.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_input_free(input: *mut RcInput) {
+    if !input.is_null() {
+        drop(unsafe { Box::from_raw(input) });
+    }
+}
+
+// Rules
+
+/// # Safety
+/// `name` must be a NUL-terminated string and `out` a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_rules_preset(name: *const c_char, out: *mut *mut RcRules) -> RcStatus {
+    guarded(|| {
+        if out.is_null() {
+            return fail(RcStatus::NullArgument, "out is NULL");
+        }
+        unsafe { *out = ptr::null_mut() };
+        let name = match unsafe { read_str(name) } {
+            Ok(name) => name,
+            Err(status) => return status,
+        };
+        match RuleSet::preset(name) {
+            Some(rules) => store(out, RcRules(rules)),
+            None => fail(
+                RcStatus::InvalidRules,
+                format!(
+                    "unknown preset '{}' (expected one of {})",
+                    name,
+                    RuleSet::PRESETS.join(", ")
+                ),
+            ),
+        }
+    })
+}
+
+/// # Safety
+/// `toml` must be a NUL-terminated string and `out` a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_rules_from_toml(
+    toml: *const c_char,
+    out: *mut *mut RcRules,
+) -> RcStatus {
+    guarded(|| {
+        if out.is_null() {
+            return fail(RcStatus::NullArgument, "out is NULL");
+        }
+        unsafe { *out = ptr::null_mut() };
+        let toml = match unsafe { read_str(toml) } {
+            Ok(toml) => toml,
+            Err(status) => return status,
+        };
+        match RuleSet::from_toml(toml) {
+            Ok(rules) => store(out, RcRules(rules)),
+            Err(err) => fail(RcStatus::InvalidRules, err),
+        }
+    })
+}
+
+/// # Safety
+/// `rules` must be NULL or a handle from rc_rules_*, freed only once.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_rules_free(rules: *mut RcRules) {
+    if !rules.is_null() {
+        drop(unsafe { Box::from_raw(rules) });
+    }
+}
+
+// Scoring
+
+/// # Safety
+/// `input` must be a live input handle and `out` a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_calculate_agari(
+    input: *const RcInput,
+    out: *mut *mut RcResult,
+) -> RcStatus {
+    unsafe { rc_calculate_agari_with_rules(input, ptr::null(), out) }
+}
+
+/// # Safety
+/// `input` must be a live input handle, `rules` NULL (default rules) or a
+/// live rules handle, and `out` a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_calculate_agari_with_rules(
+    input: *const RcInput,
+    rules: *const RcRules,
+    out: *mut *mut RcResult,
+) -> RcStatus {
+    guarded(|| {
+        if out.is_null() {
+            return fail(RcStatus::NullArgument, "out is NULL");
+        }
+        unsafe { *out = ptr::null_mut() };
+        let Some(RcInput(input)) = (unsafe { input.as_ref() }) else {
+            return fail(RcStatus::NullArgument, "input is NULL");
+        };
+        let default_rules = RuleSet::default();
+        let rules = match unsafe { rules.as_ref() } {
+            Some(RcRules(rules)) => rules,
+            None => &default_rules,
+        };
+        match calculate_agari(input, rules) {
+            Ok(result) => store(out, RcResult(result)),
+            Err(err) => fail(RcStatus::from(&err), err),
+        }
+    })
+}
+
+/// # Safety
+/// `result` must be NULL or a handle from rc_calculate_agari, freed only once.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_result_free(result: *mut RcResult) {
+    if !result.is_null() {
+        drop(unsafe { Box::from_raw(result) });
+    }
+}
+
+// Result getters (0 / -1 on a NULL handle)
+
+unsafe fn with_result<T>(
+    result: *const RcResult,
+    default: T,
+    read: impl FnOnce(&AgariResult) -> T,
+) -> T {
+    match unsafe { result.as_ref() } {
+        Some(RcResult(result)) => read(result),
+        None => default,
+    }
+}
+
+/// # Safety
+/// `result` must be NULL or a live result handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_result_han(result: *const RcResult) -> u32 {
+    unsafe { with_result(result, 0, |r| r.han as u32) }
+}
+
+/// # Safety
+/// `result` must be NULL or a live result handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_result_fu(result: *const RcResult) -> u32 {
+    unsafe { with_result(result, 0, |r| r.fu as u32) }
+}
+
+/// # Safety
+/// `result` must be NULL or a live result handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_result_total_payment(result: *const RcResult) -> u32 {
+    unsafe { with_result(result, 0, |r| r.total_payment) }
+}
+
+/// # Safety
+/// `result` must be NULL or a live result handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_result_oya_payment(result: *const RcResult) -> u32 {
+    unsafe { with_result(result, 0, |r| r.oya_payment) }
+}
+
+/// # Safety
+/// `result` must be NULL or a live result handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_result_ko_payment(result: *const RcResult) -> u32 {
+    unsafe { with_result(result, 0, |r| r.ko_payment) }
+}
+
+/// # Safety
+/// `result` must be NULL or a live result handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_result_limit(result: *const RcResult) -> u32 {
+    unsafe {
+        with_result(result, 0, |r| match r.limit_name {
+            None => 0,
+            Some(HandLimit::Mangan) => 1,
+            Some(HandLimit::Haneman) => 2,
+            Some(HandLimit::Baiman) => 3,
+            Some(HandLimit::Sanbaiman) => 4,
+            Some(HandLimit::Yakuman) => 5,
+        })
+    }
+}
+
+/// # Safety
+/// `result` must be NULL or a live result handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_result_point_delta(result: *const RcResult, seat: u8) -> i32 {
+    unsafe {
+        with_result(result, 0, |r| {
+            r.point_deltas
+                .and_then(|deltas| deltas.get(seat as usize).copied())
+                .unwrap_or(0)
+        })
+    }
+}
+
+/// # Safety
+/// `result` must be NULL or a live result handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_result_yaku_count(result: *const RcResult) -> u32 {
+    unsafe { with_result(result, 0, |r| r.yaku_list.len() as u32) }
+}
+
+/// Yaku code (RcYaku) at `index`, -1 when out of range.
+///
+/// # Safety
+/// `result` must be NULL or a live result handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_result_yaku(result: *const RcResult, index: u32) -> i32 {
+    unsafe {
+        with_result(result, -1, |r| {
+            r.yaku_list.get(index as usize).map_or(-1, yaku_code)
+        })
+    }
+}
+
+/// Serialized AgariResult, released with rc_string_free.
+///
+/// # Safety
+/// `result` must be NULL or a live result handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_result_to_json(result: *const RcResult) -> *mut c_char {
+    unsafe {
+        with_result(result, ptr::null_mut(), |r| {
+            serde_json::to_string(r)
+                .ok()
+                .and_then(|json| CString::new(json).ok())
+                .map_or(ptr::null_mut(), CString::into_raw)
+        })
+    }
+}
+
+/// # Safety
+/// `text` must be NULL or a string from rc_result_to_json, freed only once.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_string_free(text: *mut c_char) {
+    if !text.is_null() {
+        drop(unsafe { CString::from_raw(text) });
+    }
+}
+
+// Yaku codes are indices into Yaku::ALL
+
+fn yaku_code(yaku: &Yaku) -> i32 {
+    Yaku::ALL
+        .iter()
+        .position(|candidate| candidate == yaku)
+        .map_or(-1, |index| index as i32)
+}
+
+// English name of a yaku code, NULL when unknown; static storage
+#[unsafe(no_mangle)]
+pub extern "C" fn rc_yaku_name(code: i32) -> *const c_char {
+    static NAMES: OnceLock<Vec<CString>> = OnceLock::new();
+    let names = NAMES.get_or_init(|| {
+        Yaku::ALL
+            .iter()
+            .map(|yaku| CString::new(yaku.to_string()).unwrap_or_default())
+            .collect()
+    });
+    usize::try_from(code)
+        .ok()
+        .and_then(|index| names.get(index))
+        .map_or(ptr::null(), |name| name.as_ptr())
+}
+
+// Errors
+
+// RC_API_VERSION the library was built with
+#[unsafe(no_mangle)]
+pub extern "C" fn rc_api_version() -> u32 {
+    RC_API_VERSION
+}
+
+// message of the last failure on this thread; valid until the next call
+#[unsafe(no_mangle)]
+pub extern "C" fn rc_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ptr())
+}
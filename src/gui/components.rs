@@ -1,6 +1,8 @@
 use super::messages::Message;
 use super::styles::ColoredButtonStyle;
-use crate::implements::tiles::{Hai, Jihai, Kaze, Sangenpai, Suhai, Suit};
+use crate::implements::tiles::{
+    Hai, Jihai, Kaze, Sangenpai, Suhai, Suit, TILE_COUNT, index_to_tile,
+};
 use iced::Element;
 use iced::theme;
 use iced::widget::{button, column, row, text};
@@ -19,15 +21,37 @@ impl<'a> OnPressMaybe for button::Button<'a, Message> {
     }
 }
 
+// the 34 tile kinds and the three red fives
+pub fn pool_tiles() -> Vec<Hai> {
+    let mut tiles: Vec<Hai> = (0..TILE_COUNT).map(index_to_tile).collect();
+    tiles.extend(RED_FIVES);
+    tiles
+}
+
+pub const RED_FIVES: [Hai; 3] = [
+    red_five(Suit::Manzu),
+    red_five(Suit::Pinzu),
+    red_five(Suit::Souzu),
+];
+
+const fn red_five(suit: Suit) -> Hai {
+    Hai::Suhai(Suhai {
+        number: 5,
+        suit,
+        is_red: true,
+    })
+}
+
 pub fn get_tile_image_path(tile: &Hai) -> String {
     let filename = match tile {
-        Hai::Suhai(Suhai { number, suit }) => {
+        Hai::Suhai(Suhai { number, suit, .. }) => {
             let suit_prefix = match suit {
                 Suit::Manzu => "Man",
                 Suit::Pinzu => "Pin",
                 Suit::Souzu => "Sou",
             };
-            format!("{}{}.png", suit_prefix, number)
+            let marker = if tile.is_red() { "-Dora" } else { "" };
+            format!("{}{}{}.png", suit_prefix, number, marker)
         }
         Hai::Jihai(jihai) => {
             let name = match jihai {
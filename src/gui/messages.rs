@@ -10,6 +10,7 @@ pub enum Message {
     RemoveTile(usize),
     ConfirmHand,
     CancelSelection,
+    ToggleSanma(bool),
 
     // Definition Phase
     ModifyHand,
@@ -26,11 +27,11 @@ pub enum Message {
     SelectOpenKan(Hai),
     RemoveOpenMeld(usize),
     RemoveClosedKan(usize),
-    IncrementAkadora,
-    DecrementAkadora,
     ToggleAgariType(AgariType),
     SetBakaze(Kaze),
     SetJikaze(Kaze),
+    SetOyaKaze(Kaze),
+    SetDiscarder(Kaze),
     ToggleRiichi(bool),
     ToggleDoubleRiichi(bool),
     ToggleIppatsu(bool),
@@ -43,6 +44,10 @@ pub enum Message {
     ToggleRenhou(bool),
     IncrementHonba,
     DecrementHonba,
+    IncrementKyoutaku,
+    DecrementKyoutaku,
+    IncrementKita,
+    DecrementKita,
     StartAddDora,
     SelectDora(Hai),
     StartAddUraDora,
@@ -12,16 +12,18 @@ use self::view::View;
 use iced::{Element, Sandbox, Settings};
 
 pub fn run() -> iced::Result {
-    let mut settings = Settings::default();
-    settings.fonts = vec![
-        include_bytes!("../../assets/font/Arimo.ttf")
-            .as_slice()
-            .into(),
-        include_bytes!("../../assets/font/Arimo-Bold.ttf")
-            .as_slice()
-            .into(),
-    ];
-    settings.default_font = iced::Font::with_name("Arimo");
+    let settings = Settings {
+        fonts: vec![
+            include_bytes!("../../assets/font/Arimo.ttf")
+                .as_slice()
+                .into(),
+            include_bytes!("../../assets/font/Arimo-Bold.ttf")
+                .as_slice()
+                .into(),
+        ],
+        default_font: iced::Font::with_name("Arimo"),
+        ..Settings::default()
+    };
     RiichiGui::run(settings)
 }
 
@@ -1,8 +1,9 @@
 use super::RiichiGui;
 use crate::implements::calculate_agari;
+use crate::implements::types::error::AgariError;
 use crate::implements::types::game::{AgariType, GameContext, PlayerContext};
-use crate::implements::types::input::UserInput;
-use crate::implements::types::tiles::{Hai, Kaze, Suhai, index_to_tile, tile_to_index};
+use crate::implements::types::input::{ClosedKanInput, UserInput};
+use crate::implements::types::tiles::{Hai, Suhai, index_to_tile, tile_to_index};
 
 impl RiichiGui {
     /// construct UserInput and calculate score
@@ -20,8 +21,7 @@ impl RiichiGui {
 
             // build hand tiles from counts
             let mut hand_tiles = Vec::with_capacity(14);
-            for i in 0..34 {
-                let count = counts[i];
+            for (i, &count) in counts.iter().enumerate() {
                 if count > 0 {
                     let tile = index_to_tile(i);
                     for _ in 0..count {
@@ -31,26 +31,34 @@ impl RiichiGui {
             }
 
             // build UserInput
-            let input = UserInput {
+            let mut input = UserInput {
                 hand_tiles: hand_tiles.clone(),
                 open_melds: self.open_melds.clone(),
-                closed_kans: self.closed_kans.clone(),
+                closed_kans: self.closed_kan_inputs(),
                 winning_tile,
                 agari_type: self.agari_type,
                 player_context: PlayerContext {
                     jikaze: self.jikaze,
-                    is_oya: self.jikaze == Kaze::Ton,
+                    is_oya: self.jikaze == self.oya_kaze,
                     is_riichi: self.is_riichi,
                     is_daburu_riichi: self.is_daburu_riichi,
                     is_ippatsu: self.is_ippatsu,
+                    is_open_riichi: false,
                     is_menzen: self.open_melds.is_empty(),
+                    num_kita: self.num_kita,
                 },
                 game_context: GameContext {
                     bakaze: self.bakaze,
+                    oya_kaze: self.oya_kaze,
                     honba: self.honba,
+                    kyoutaku: self.kyoutaku,
+                    discarder: match self.agari_type {
+                        AgariType::Ron => self.discarder,
+                        AgariType::Tsumo => None,
+                    },
+                    is_discarder_riichi: false,
                     dora_indicators: self.dora_indicators.clone(),
                     uradora_indicators: self.uradora_indicators.clone(),
-                    num_akadora: self.num_akadora,
                     is_tenhou: self.is_tenhou,
                     is_chiihou: self.is_chiihou,
                     is_renhou: self.is_renhou,
@@ -60,38 +68,47 @@ impl RiichiGui {
                     is_chankan: self.is_chankan,
                 },
             };
+            self.place_red_fives(&mut input);
 
-            let mut best_result = calculate_agari(&input);
+            let rules = self.rules();
+            let mut best_result = calculate_agari(&input, &rules);
 
             // fallback Winning Tile in Open Melds
-            if best_result.is_err() {
+            if matches!(best_result, Err(ref e) if !matches!(e, AgariError::NoYaku { .. })) {
                 let base_open_melds = self.open_melds.clone();
 
                 for (i, meld) in base_open_melds.iter().enumerate() {
                     let meld_tiles = self.get_meld_tiles(meld);
-                    if meld_tiles.contains(&winning_tile) {
+                    if meld_tiles.contains(&winning_tile.plain()) {
                         let mut alt_hand_tiles = hand_tiles.clone();
                         alt_hand_tiles.extend(meld_tiles.iter());
 
                         // this meld turns concealed
-                        if let Some(pos) = alt_hand_tiles.iter().position(|x| *x == winning_tile) {
+                        if let Some(pos) = alt_hand_tiles
+                            .iter()
+                            .position(|x| *x == winning_tile.plain())
+                        {
                             alt_hand_tiles.remove(pos);
                         }
 
                         let mut alt_open_melds = base_open_melds.clone();
                         alt_open_melds.remove(i);
 
-                        let alt_input = UserInput {
+                        let mut alt_input = UserInput {
                             hand_tiles: alt_hand_tiles,
+                            player_context: PlayerContext {
+                                is_menzen: alt_open_melds.is_empty(),
+                                ..input.player_context
+                            },
                             open_melds: alt_open_melds,
                             winning_tile,
-                            closed_kans: self.closed_kans.clone(),
+                            closed_kans: self.closed_kan_inputs(),
                             agari_type: self.agari_type,
-                            player_context: input.player_context.clone(),
                             game_context: input.game_context.clone(),
                         };
+                        self.place_red_fives(&mut alt_input);
 
-                        if let Ok(res) = calculate_agari(&alt_input) {
+                        if let Ok(res) = calculate_agari(&alt_input, &rules) {
                             best_result = Ok(res);
                             break;
                         }
@@ -100,57 +117,78 @@ impl RiichiGui {
             }
 
             // result
-            self.score_result = match best_result {
-                Ok(result) => Some(Ok(result)),
-                Err(e) => Some(Err(format!("Error: {}", e))),
-            };
+            self.score_result = Some(best_result);
             self.phase = super::Phase::Result;
         }
     }
 
-    /// dynamic counting
-    pub fn get_max_akadora_count(&self) -> u8 {
-        let mut count_5m = 0;
-        let mut count_5p = 0;
-        let mut count_5s = 0;
-
-        // helper: +1 for 5m, 5p, 5s
-        let check_tile = |tile: &Hai, c_m: &mut u8, c_p: &mut u8, c_s: &mut u8| {
-            if let Hai::Suhai(Suhai { number: 5, suit }) = tile {
-                match suit {
-                    crate::implements::types::tiles::Suit::Manzu => *c_m += 1,
-                    crate::implements::types::tiles::Suit::Pinzu => *c_p += 1,
-                    crate::implements::types::tiles::Suit::Souzu => *c_s += 1,
-                }
-            }
-        };
+    fn closed_kan_inputs(&self) -> Vec<ClosedKanInput> {
+        self.closed_kans
+            .iter()
+            .map(|&tile| ClosedKanInput {
+                tile,
+                num_akadora: 0,
+            })
+            .collect()
+    }
 
-        // scan
-        for tile in &self.hand_tiles {
-            check_tile(tile, &mut count_5m, &mut count_5p, &mut count_5s);
+    /// red fives picked for the hand go to the winning tile first,
+    /// then the concealed tiles, the melds and the closed kans
+    fn place_red_fives(&self, input: &mut UserInput) {
+        let five_suit = |tile: &Hai| match tile {
+            Hai::Suhai(Suhai {
+                number: 5, suit, ..
+            }) => Some(*suit as usize),
+            _ => None,
+        };
+        let mut reds = [0u8; 3];
+        for suit in self
+            .hand_tiles
+            .iter()
+            .filter(|t| t.is_red())
+            .filter_map(five_suit)
+        {
+            reds[suit] += 1;
         }
 
-        if let Some(tile) = &self.winning_tile {
-            check_tile(tile, &mut count_5m, &mut count_5p, &mut count_5s);
+        let winning_tile = input.winning_tile;
+        if winning_tile.is_red()
+            && let Some(suit) = five_suit(&winning_tile)
+        {
+            reds[suit] = reds[suit].saturating_sub(1);
         }
 
-        for meld in &self.open_melds {
-            for tile in self.get_meld_tiles(meld) {
-                check_tile(&tile, &mut count_5m, &mut count_5p, &mut count_5s);
+        // a Tsumo hand holds the winning tile itself
+        let mut holds_winning_tile = input.agari_type == AgariType::Tsumo;
+        for tile in &mut input.hand_tiles {
+            if holds_winning_tile && *tile == winning_tile.plain() {
+                *tile = winning_tile;
+                holds_winning_tile = false;
+            } else if let Some(suit) = five_suit(tile)
+                && reds[suit] > 0
+            {
+                reds[suit] -= 1;
+                *tile = tile.red();
             }
         }
 
-        for tile in &self.closed_kans {
-            for _ in 0..4 {
-                check_tile(tile, &mut count_5m, &mut count_5p, &mut count_5s);
+        for meld in &mut input.open_melds {
+            let fives: Vec<usize> = self
+                .get_meld_tiles(meld)
+                .iter()
+                .filter_map(five_suit)
+                .collect();
+            if let Some(&suit) = fives.first() {
+                meld.num_akadora = reds[suit].min(fives.len() as u8);
+                reds[suit] -= meld.num_akadora;
             }
         }
 
-        // 1 red 5m, 2 red 5p, 1 red 5s
-        let max_m = if count_5m > 0 { 1 } else { 0 };
-        let max_p = if count_5p >= 2 { 2 } else { count_5p };
-        let max_s = if count_5s > 0 { 1 } else { 0 };
-
-        max_m + max_p + max_s
+        for kan in &mut input.closed_kans {
+            if let Some(suit) = five_suit(&kan.tile) {
+                kan.num_akadora = reds[suit].min(4);
+                reds[suit] -= kan.num_akadora;
+            }
+        }
     }
 }
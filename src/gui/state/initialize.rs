@@ -3,6 +3,7 @@ use super::phase::Phase;
 use crate::implements::types::{
     game::AgariType,
     input::OpenMeldInput,
+    rules::RuleSet,
     tiles::{Hai, Kaze},
 };
 
@@ -16,6 +17,7 @@ struct GameStateDefaults {
     agari_type: AgariType,
     bakaze: Kaze,
     jikaze: Kaze,
+    oya_kaze: Kaze,
     is_riichi: bool,
     is_daburu_riichi: bool,
     is_ippatsu: bool,
@@ -27,10 +29,17 @@ struct GameStateDefaults {
     is_chiihou: bool,
     is_renhou: bool,
     honba: u8,
-    num_akadora: u8,
+    kyoutaku: u8,
+    num_kita: u8,
+    discarder: Option<Kaze>,
     dora_indicators: Vec<Hai>,
     uradora_indicators: Vec<Hai>,
-    score_result: Option<Result<crate::implements::types::scoring::AgariResult, String>>,
+    score_result: Option<
+        Result<
+            crate::implements::types::scoring::AgariResult,
+            crate::implements::error::AgariError,
+        >,
+    >,
     show_rules: bool,
 }
 
@@ -41,10 +50,9 @@ impl RiichiGui {
         // image loading and resizing
         let ((tile_images, tile_images_sideways), (rules_image, tile_back_image)) = rayon::join(
             || {
-                (0..34)
+                crate::gui::components::pool_tiles()
                     .into_par_iter()
-                    .map(|i| {
-                        let tile = crate::implements::types::tiles::index_to_tile(i);
+                    .map(|tile| {
                         let path = crate::gui::components::get_tile_image_path(&tile);
 
                         // fast render
@@ -122,12 +130,15 @@ impl RiichiGui {
             phase: defaults.phase,
             hand_tiles: defaults.hand_tiles,
             tile_counts: [4; 34],
+            red_counts: RuleSet::default().red_fives,
+            is_sanma: false,
             winning_tile: defaults.winning_tile,
             open_melds: defaults.open_melds,
             closed_kans: defaults.closed_kans,
             agari_type: defaults.agari_type,
             bakaze: defaults.bakaze,
             jikaze: defaults.jikaze,
+            oya_kaze: defaults.oya_kaze,
             is_riichi: defaults.is_riichi,
             is_daburu_riichi: defaults.is_daburu_riichi,
             is_ippatsu: defaults.is_ippatsu,
@@ -139,7 +150,9 @@ impl RiichiGui {
             is_chiihou: defaults.is_chiihou,
             is_renhou: defaults.is_renhou,
             honba: defaults.honba,
-            num_akadora: defaults.num_akadora,
+            kyoutaku: defaults.kyoutaku,
+            num_kita: defaults.num_kita,
+            discarder: defaults.discarder,
             dora_indicators: defaults.dora_indicators,
             uradora_indicators: defaults.uradora_indicators,
             score_result: defaults.score_result,
@@ -156,12 +169,14 @@ impl RiichiGui {
         self.phase = defaults.phase;
         self.hand_tiles = defaults.hand_tiles;
         self.tile_counts = [4; 34];
+        self.red_counts = self.rules().red_fives;
         self.winning_tile = defaults.winning_tile;
         self.open_melds = defaults.open_melds;
         self.closed_kans = defaults.closed_kans;
         self.agari_type = defaults.agari_type;
         self.bakaze = defaults.bakaze;
         self.jikaze = defaults.jikaze;
+        self.oya_kaze = defaults.oya_kaze;
         self.is_riichi = defaults.is_riichi;
         self.is_daburu_riichi = defaults.is_daburu_riichi;
         self.is_ippatsu = defaults.is_ippatsu;
@@ -173,7 +188,9 @@ impl RiichiGui {
         self.is_chiihou = defaults.is_chiihou;
         self.is_renhou = defaults.is_renhou;
         self.honba = defaults.honba;
-        self.num_akadora = defaults.num_akadora;
+        self.kyoutaku = defaults.kyoutaku;
+        self.num_kita = defaults.num_kita;
+        self.discarder = defaults.discarder;
         self.dora_indicators = defaults.dora_indicators;
         self.uradora_indicators = defaults.uradora_indicators;
         self.score_result = defaults.score_result;
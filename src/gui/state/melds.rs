@@ -3,8 +3,7 @@ use crate::implements::types::{
     hand::MentsuType,
     input::OpenMeldInput,
     tiles::{
-        Hai, MAX_SHUNTSU_START, SUHAI_TILES_COUNT, TILE_COUNT, TILES_PER_SUHAI, index_to_tile,
-        tile_to_index,
+        Hai, MAX_SHUNTSU_START, SUHAI_TILES_COUNT, TILES_PER_SUHAI, index_to_tile, tile_to_index,
     },
 };
 
@@ -62,13 +61,14 @@ impl RiichiGui {
         let available_counts = self.get_active_hand_counts();
 
         let mut pons = Vec::new();
-        for i in 0..TILE_COUNT {
-            if available_counts[i] >= 3 {
+        for (i, &count) in available_counts.iter().enumerate() {
+            if count >= 3 {
                 let tile = index_to_tile(i);
                 pons.push(OpenMeldInput {
                     mentsu_type: MentsuType::Koutsu,
                     representative_tile: tile,
                     is_added_kan: false,
+                    num_akadora: 0,
                 });
             }
         }
@@ -94,6 +94,7 @@ impl RiichiGui {
                         mentsu_type: MentsuType::Shuntsu,
                         representative_tile: tile,
                         is_added_kan: false,
+                        num_akadora: 0,
                     });
                 }
             }
@@ -105,8 +106,8 @@ impl RiichiGui {
         let available_counts = self.get_active_hand_counts();
 
         let mut kans = Vec::new();
-        for i in 0..TILE_COUNT {
-            if available_counts[i] == 4 {
+        for (i, &count) in available_counts.iter().enumerate() {
+            if count == 4 {
                 let tile = index_to_tile(i);
                 kans.push(tile);
             }
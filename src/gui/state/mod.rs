@@ -17,6 +17,8 @@ pub struct RiichiGui {
     // Composition Phase
     pub hand_tiles: Vec<Hai>,
     pub tile_counts: [u8; 34],
+    pub red_counts: [u8; 3], // red fives left in the pool (man, pin, sou)
+    pub is_sanma: bool,      // three-player set (no 2m-8m)
 
     // Definition Phase
     pub winning_tile: Option<Hai>,
@@ -27,6 +29,7 @@ pub struct RiichiGui {
     pub agari_type: AgariType,
     pub bakaze: Kaze,
     pub jikaze: Kaze,
+    pub oya_kaze: Kaze,
     pub is_riichi: bool,
     pub is_daburu_riichi: bool,
     pub is_ippatsu: bool,
@@ -38,10 +41,17 @@ pub struct RiichiGui {
     pub is_chiihou: bool,
     pub is_renhou: bool,
     pub honba: u8,
-    pub num_akadora: u8,
+    pub kyoutaku: u8,
+    pub num_kita: u8,
+    pub discarder: Option<Kaze>,
     pub dora_indicators: Vec<Hai>,
     pub uradora_indicators: Vec<Hai>,
-    pub score_result: Option<Result<crate::implements::types::scoring::AgariResult, String>>,
+    pub score_result: Option<
+        Result<
+            crate::implements::types::scoring::AgariResult,
+            crate::implements::error::AgariError,
+        >,
+    >,
     pub show_rules: bool,
     pub tile_images: std::collections::HashMap<Hai, iced::widget::image::Handle>,
     pub tile_images_sideways: std::collections::HashMap<Hai, iced::widget::image::Handle>,
@@ -1,7 +1,8 @@
 use crate::implements::types::hand::MentsuType;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum Phase {
+    #[default]
     Composition,
     Definition,
     SelectingWinningTile,
@@ -14,9 +15,3 @@ pub enum Phase {
     SelectingUraDora,
     Result,
 }
-
-impl Default for Phase {
-    fn default() -> Self {
-        Phase::Composition
-    }
-}
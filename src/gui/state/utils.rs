@@ -1,7 +1,62 @@
 use super::super::state::RiichiGui;
-use crate::implements::types::tiles::TILE_COUNT;
+use crate::implements::types::rules::RuleSet;
+use crate::implements::types::tiles::{Hai, Suhai, TILE_COUNT, index_to_tile, tile_to_index};
 
 impl RiichiGui {
+    /// standard rules, or three-player rules with the sanma set
+    pub fn rules(&self) -> RuleSet {
+        if self.is_sanma {
+            RuleSet::preset("sanma").expect("sanma is a bundled preset")
+        } else {
+            RuleSet::default()
+        }
+    }
+
+    /// tiles shown for picking, one row per suit
+    pub fn tile_rows(&self) -> Vec<Vec<Hai>> {
+        (0..TILE_COUNT)
+            .step_by(9)
+            .map(|start| {
+                (start..TILE_COUNT.min(start + 9))
+                    .map(index_to_tile)
+                    .filter(|tile| !self.is_sanma || tile.is_sanma_tile())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// tiles of this kind left in the pool; red fives are counted apart
+    pub fn pool_count(&self, tile: &Hai) -> u8 {
+        let left = self.tile_counts[tile_to_index(tile)];
+        match tile {
+            Hai::Suhai(Suhai {
+                number: 5, suit, ..
+            }) => {
+                let reds = self.red_counts[*suit as usize];
+                if tile.is_red() { reds } else { left - reds }
+            }
+            _ => left,
+        }
+    }
+
+    pub fn take_from_pool(&mut self, tile: &Hai) {
+        self.tile_counts[tile_to_index(tile)] -= 1;
+        if let Hai::Suhai(Suhai { suit, .. }) = tile
+            && tile.is_red()
+        {
+            self.red_counts[*suit as usize] -= 1;
+        }
+    }
+
+    pub fn return_to_pool(&mut self, tile: &Hai) {
+        self.tile_counts[tile_to_index(tile)] += 1;
+        if let Hai::Suhai(Suhai { suit, .. }) = tile
+            && tile.is_red()
+        {
+            self.red_counts[*suit as usize] += 1;
+        }
+    }
+
     /// available tiles for melds
     pub fn get_active_hand_counts(&self) -> [u8; TILE_COUNT] {
         let mut counts = [0u8; TILE_COUNT];
@@ -30,4 +85,14 @@ impl RiichiGui {
 
         counts
     }
+
+    /// reset Tenhou/Chiihou/Renhou after a seat change
+    pub fn reset_dealer_flags(&mut self) {
+        if self.jikaze == self.oya_kaze {
+            self.is_chiihou = false;
+            self.is_renhou = false;
+        } else {
+            self.is_tenhou = false;
+        }
+    }
 }
@@ -4,7 +4,7 @@ use super::state::{Phase, RiichiGui};
 use crate::implements::game::AgariType;
 use crate::implements::hand::MentsuType;
 use crate::implements::input::OpenMeldInput;
-use crate::implements::tiles::Kaze;
+use crate::implements::tiles::{Hai, Jihai, Kaze};
 
 pub trait Update {
     fn update(&mut self, message: Message);
@@ -15,20 +15,16 @@ impl Update for RiichiGui {
         match message {
             // Composition Phase
             Message::AddTile(tile) => {
-                if self.hand_tiles.len() < 18 {
-                    let idx = crate::implements::tiles::tile_to_index(&tile);
-                    if self.tile_counts[idx] > 0 {
-                        self.tile_counts[idx] -= 1;
-                        insert_tile_sorted(&mut self.hand_tiles, tile);
-                    }
+                if self.hand_tiles.len() < 18 && self.pool_count(&tile) > 0 {
+                    self.take_from_pool(&tile);
+                    insert_tile_sorted(&mut self.hand_tiles, tile);
                 }
             }
             Message::RemoveTile(index) => {
                 // Remove tile
                 if index < self.hand_tiles.len() {
                     let tile = self.hand_tiles.remove(index);
-                    let idx = crate::implements::tiles::tile_to_index(&tile);
-                    self.tile_counts[idx] += 1;
+                    self.return_to_pool(&tile);
                 }
             }
             Message::ConfirmHand => {
@@ -40,6 +36,13 @@ impl Update for RiichiGui {
             Message::CancelSelection => {
                 self.phase = Phase::Definition;
             }
+            Message::ToggleSanma(is_sanma) => {
+                // the tile set changes, so the hand starts over
+                self.is_sanma = is_sanma;
+                self.hand_tiles.clear();
+                self.tile_counts = [4; 34];
+                self.red_counts = self.rules().red_fives;
+            }
 
             // Definition Phase
             Message::ModifyHand => {
@@ -54,8 +57,11 @@ impl Update for RiichiGui {
                 self.agari_type = AgariType::Ron;
                 self.bakaze = Kaze::Ton;
                 self.jikaze = Kaze::Ton;
+                self.oya_kaze = Kaze::Ton;
                 self.honba = 0;
-                self.num_akadora = 0;
+                self.kyoutaku = 0;
+                self.num_kita = 0;
+                self.discarder = None;
 
                 self.is_riichi = false;
                 self.is_daburu_riichi = false;
@@ -84,19 +90,19 @@ impl Update for RiichiGui {
             }
             Message::SelectCompleteMeld(meld) => {
                 // Add a meld
-                if let Phase::SelectingMeldTile(_) = self.phase {
-                    if self.can_form_meld(&meld) {
-                        self.open_melds.push(meld);
+                if let Phase::SelectingMeldTile(_) = self.phase
+                    && self.can_form_meld(&meld)
+                {
+                    self.open_melds.push(meld);
 
-                        // Reset if open hand
-                        if !self.open_melds.is_empty() {
-                            self.is_riichi = false;
-                            self.is_daburu_riichi = false;
-                            self.is_ippatsu = false;
-                            self.is_tenhou = false;
-                            self.is_chiihou = false;
-                            self.is_renhou = false;
-                        }
+                    // Reset if open hand
+                    if !self.open_melds.is_empty() {
+                        self.is_riichi = false;
+                        self.is_daburu_riichi = false;
+                        self.is_ippatsu = false;
+                        self.is_tenhou = false;
+                        self.is_chiihou = false;
+                        self.is_renhou = false;
                     }
                 }
                 self.phase = Phase::Definition;
@@ -117,7 +123,7 @@ impl Update for RiichiGui {
             Message::SelectAddedKan(index) => {
                 if index < self.open_melds.len() {
                     let tile = self.open_melds[index].representative_tile;
-                    let count = self.hand_tiles.iter().filter(|&&t| t == tile).count();
+                    let count = self.hand_tiles.iter().filter(|t| t.plain() == tile).count();
                     if count >= 4 {
                         if let Some(meld) = self.open_melds.get_mut(index) {
                             meld.mentsu_type = MentsuType::Kantsu;
@@ -135,6 +141,7 @@ impl Update for RiichiGui {
                     mentsu_type: MentsuType::Kantsu,
                     representative_tile: tile,
                     is_added_kan: false,
+                    num_akadora: 0,
                 };
                 self.open_melds.push(meld);
 
@@ -176,6 +183,7 @@ impl Update for RiichiGui {
                         self.is_rinshan = false;
                     }
                     AgariType::Tsumo => {
+                        self.discarder = None;
                         self.is_renhou = false;
                         self.is_houtei = false;
                         self.is_chankan = false;
@@ -187,11 +195,18 @@ impl Update for RiichiGui {
             }
             Message::SetJikaze(kaze) => {
                 self.jikaze = kaze;
-                // Reset Tenhou/Chiihou
-                if self.jikaze != Kaze::Ton {
-                    self.is_tenhou = false;
-                } else {
-                    self.is_chiihou = false;
+                if self.discarder == Some(kaze) {
+                    self.discarder = None;
+                }
+                self.reset_dealer_flags();
+            }
+            Message::SetOyaKaze(kaze) => {
+                self.oya_kaze = kaze;
+                self.reset_dealer_flags();
+            }
+            Message::SetDiscarder(kaze) => {
+                if kaze != self.jikaze {
+                    self.discarder = Some(kaze);
                 }
             }
             Message::ToggleRiichi(is_riichi) => {
@@ -237,6 +252,24 @@ impl Update for RiichiGui {
                     self.honba -= 1
                 }
             }
+            Message::IncrementKyoutaku => self.kyoutaku += 1,
+            Message::DecrementKyoutaku => {
+                if self.kyoutaku > 0 {
+                    self.kyoutaku -= 1
+                }
+            }
+            Message::IncrementKita => {
+                // North tiles not in the hand
+                let north = Hai::Jihai(Jihai::Kaze(Kaze::Pei));
+                if self.num_kita < self.pool_count(&north) {
+                    self.num_kita += 1
+                }
+            }
+            Message::DecrementKita => {
+                if self.num_kita > 0 {
+                    self.num_kita -= 1
+                }
+            }
             Message::StartAddDora => self.phase = Phase::SelectingDora,
             Message::SelectDora(tile) => {
                 self.dora_indicators.push(tile);
@@ -274,16 +307,6 @@ impl Update for RiichiGui {
             Message::HideRules => {
                 self.show_rules = false;
             }
-            Message::IncrementAkadora => {
-                if self.num_akadora < 4 {
-                    self.num_akadora += 1;
-                }
-            }
-            Message::DecrementAkadora => {
-                if self.num_akadora > 0 {
-                    self.num_akadora -= 1;
-                }
-            }
         }
     }
 }
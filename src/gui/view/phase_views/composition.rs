@@ -1,7 +1,7 @@
 use crate::gui::messages::Message;
 use crate::gui::state::RiichiGui;
 use crate::gui::styles::ColoredButtonStyle;
-use iced::widget::{button, column, text};
+use iced::widget::{button, checkbox, column, text};
 use iced::{Color, Element, Length, theme};
 
 pub fn build_composition_view(gui: &RiichiGui) -> Element<'_, Message> {
@@ -30,10 +30,15 @@ pub fn build_composition_view(gui: &RiichiGui) -> Element<'_, Message> {
             None
         });
 
+    // switching the tile set clears the hand
+    let sanma_toggle =
+        checkbox("Three-Player (no 2m-8m)", gui.is_sanma).on_toggle(Message::ToggleSanma);
+
     column![
         counter_text,
         hand_preview,
         confirm_btn,
+        sanma_toggle,
         tile_pool,
         iced::widget::Space::with_height(Length::Fixed(100.0))
     ]
@@ -1,6 +1,7 @@
 use crate::gui::messages::Message;
-use iced::widget::{checkbox, row, text};
-use iced::{Color, Element};
+use crate::gui::styles::ColoredButtonStyle;
+use iced::widget::{button, checkbox, row, text};
+use iced::{Color, Element, theme};
 
 pub fn section_header(title: &str) -> Element<'_, Message> {
     text(title)
@@ -30,3 +31,13 @@ pub fn checkbox_with_conflict<'a>(
         .into()
     }
 }
+
+pub fn decrement_button<'a>(enabled: bool, message: Message) -> iced::widget::Button<'a, Message> {
+    button(text("-"))
+        .style(theme::Button::Custom(Box::new(ColoredButtonStyle {
+            background_color: Color::from_rgb(0.6, 0.0, 0.0),
+            text_color: Color::WHITE,
+            hover_color: None,
+        })))
+        .on_press_maybe(if enabled { Some(message) } else { None })
+}
@@ -1,10 +1,10 @@
-use super::common::section_header;
+use super::common::{decrement_button, section_header};
 use crate::gui::components::{action_button, tile_image_button};
 use crate::gui::messages::Message;
 use crate::gui::state::RiichiGui;
 use crate::gui::styles::ColoredButtonStyle;
-use iced::widget::{button, column, row, text};
-use iced::{Color, Element, theme};
+use iced::widget::{column, row, text};
+use iced::{Element, theme};
 
 pub fn build_dora_section(gui: &RiichiGui) -> Element<'_, Message> {
     column![
@@ -63,37 +63,23 @@ pub fn build_dora_section(gui: &RiichiGui) -> Element<'_, Message> {
         ]
         .spacing(5)
         .align_items(iced::Alignment::Center),
+        // 抜き北: North tiles set aside in a three-player game
+        if gui.is_sanma {
+            row![
+                text(format!("Kita: {}", gui.num_kita)),
+                action_button("+", Message::IncrementKita, ColoredButtonStyle::INFO),
+                decrement_button(gui.num_kita > 0, Message::DecrementKita),
+            ]
+            .spacing(10)
+            .align_items(iced::Alignment::Center)
+        } else {
+            row![]
+        },
         {
-            let max_akadora = gui.get_max_akadora_count();
-
-            if max_akadora > 0 {
-                row![
-                    text(format!("Red Dora: {}", gui.num_akadora)),
-                    button(text("+"))
-                        .style(theme::Button::Custom(Box::new(ColoredButtonStyle {
-                            background_color: Color::from_rgb(0.0, 0.0, 0.6),
-                            text_color: Color::WHITE,
-                            hover_color: None,
-                        })))
-                        .on_press_maybe(if gui.num_akadora < max_akadora && gui.num_akadora < 4 {
-                            Some(Message::IncrementAkadora)
-                        } else {
-                            None
-                        }),
-                    button(text("-"))
-                        .style(theme::Button::Custom(Box::new(ColoredButtonStyle {
-                            background_color: Color::from_rgb(0.6, 0.0, 0.0),
-                            text_color: Color::WHITE,
-                            hover_color: None,
-                        })))
-                        .on_press_maybe(if gui.num_akadora > 0 {
-                            Some(Message::DecrementAkadora)
-                        } else {
-                            None
-                        }),
-                ]
-                .spacing(10)
-                .align_items(iced::Alignment::Center)
+            // red fives are picked as tiles in the hand
+            let num_akadora = gui.hand_tiles.iter().filter(|t| t.is_red()).count();
+            if num_akadora > 0 {
+                row![text(format!("Red Dora: {}", num_akadora))]
             } else {
                 row![]
             }
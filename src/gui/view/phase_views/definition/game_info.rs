@@ -1,4 +1,4 @@
-use super::common::section_header;
+use super::common::{decrement_button, section_header};
 use crate::gui::components::action_button;
 use crate::gui::messages::Message;
 use crate::gui::state::RiichiGui;
@@ -6,74 +6,83 @@ use crate::gui::styles::ColoredButtonStyle;
 use crate::implements::game::AgariType;
 use crate::implements::tiles::Kaze;
 use iced::alignment::Horizontal;
-use iced::widget::{button, column, container, radio, row, text};
-use iced::{Alignment, Color, Element, Length, theme};
+use iced::widget::{Row, column, container, radio, row, text};
+use iced::{Alignment, Element, Length};
 
 pub fn build_game_info_section(gui: &RiichiGui) -> Element<'_, Message> {
     let label_width = Length::Fixed(140.0);
 
+    let mut wind_rows = column![
+        row![
+            text("Win Type:")
+                .width(label_width)
+                .horizontal_alignment(Horizontal::Right),
+            radio(
+                "Ron",
+                AgariType::Ron,
+                Some(gui.agari_type),
+                Message::ToggleAgariType
+            ),
+            radio(
+                "Tsumo",
+                AgariType::Tsumo,
+                Some(gui.agari_type),
+                Message::ToggleAgariType
+            ),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+        row![
+            text("Prevalent Wind:")
+                .width(label_width)
+                .horizontal_alignment(Horizontal::Right),
+            radio("East", Kaze::Ton, Some(gui.bakaze), Message::SetBakaze),
+            radio("South", Kaze::Nan, Some(gui.bakaze), Message::SetBakaze),
+            radio("West", Kaze::Shaa, Some(gui.bakaze), Message::SetBakaze),
+            radio("North", Kaze::Pei, Some(gui.bakaze), Message::SetBakaze),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+        seat_row(
+            "Seat Wind:",
+            gui,
+            Some(gui.jikaze),
+            None,
+            Message::SetJikaze
+        ),
+        seat_row(
+            "Dealer Seat:",
+            gui,
+            Some(gui.oya_kaze),
+            None,
+            Message::SetOyaKaze
+        ),
+    ]
+    .spacing(15)
+    .align_items(Alignment::Start);
+
+    // Ron only: who dealt in
+    if gui.agari_type == AgariType::Ron {
+        wind_rows = wind_rows.push(seat_row(
+            "Discarder:",
+            gui,
+            gui.discarder,
+            Some(gui.jikaze),
+            Message::SetDiscarder,
+        ));
+    }
+
     container(
         column![
             section_header("Game Info"),
-            column![
-                row![
-                    text("Win Type:")
-                        .width(label_width)
-                        .horizontal_alignment(Horizontal::Right),
-                    radio(
-                        "Ron",
-                        AgariType::Ron,
-                        Some(gui.agari_type),
-                        Message::ToggleAgariType
-                    ),
-                    radio(
-                        "Tsumo",
-                        AgariType::Tsumo,
-                        Some(gui.agari_type),
-                        Message::ToggleAgariType
-                    ),
-                ]
-                .spacing(10)
-                .align_items(Alignment::Center),
-                row![
-                    text("Prevalent Wind:")
-                        .width(label_width)
-                        .horizontal_alignment(Horizontal::Right),
-                    radio("East", Kaze::Ton, Some(gui.bakaze), Message::SetBakaze),
-                    radio("South", Kaze::Nan, Some(gui.bakaze), Message::SetBakaze),
-                    radio("West", Kaze::Shaa, Some(gui.bakaze), Message::SetBakaze),
-                    radio("North", Kaze::Pei, Some(gui.bakaze), Message::SetBakaze),
-                ]
-                .spacing(10)
-                .align_items(Alignment::Center),
-                row![
-                    text("Seat Wind:")
-                        .width(label_width)
-                        .horizontal_alignment(Horizontal::Right),
-                    radio("East", Kaze::Ton, Some(gui.jikaze), Message::SetJikaze),
-                    radio("South", Kaze::Nan, Some(gui.jikaze), Message::SetJikaze),
-                    radio("West", Kaze::Shaa, Some(gui.jikaze), Message::SetJikaze),
-                    radio("North", Kaze::Pei, Some(gui.jikaze), Message::SetJikaze),
-                ]
-                .spacing(10)
-                .align_items(Alignment::Center),
-            ]
-            .spacing(15)
-            .align_items(Alignment::Start),
+            wind_rows,
             row![
                 text(format!("Honba: {}", gui.honba)),
                 action_button("+", Message::IncrementHonba, ColoredButtonStyle::INFO,),
-                button(text("-"))
-                    .style(theme::Button::Custom(Box::new(ColoredButtonStyle {
-                        background_color: Color::from_rgb(0.6, 0.0, 0.0),
-                        text_color: Color::WHITE,
-                        hover_color: None,
-                    })))
-                    .on_press_maybe(if gui.honba > 0 {
-                        Some(Message::DecrementHonba)
-                    } else {
-                        None
-                    }),
+                decrement_button(gui.honba > 0, Message::DecrementHonba),
+                text(format!("Riichi Sticks: {}", gui.kyoutaku)),
+                action_button("+", Message::IncrementKyoutaku, ColoredButtonStyle::INFO,),
+                decrement_button(gui.kyoutaku > 0, Message::DecrementKyoutaku),
             ]
             .spacing(10)
             .align_items(Alignment::Center),
@@ -85,3 +94,32 @@ pub fn build_game_info_section(gui: &RiichiGui) -> Element<'_, Message> {
     .align_x(Horizontal::Center)
     .into()
 }
+
+// one radio per seat at the table (三人麻雀 has no North seat)
+fn seat_row<'a>(
+    label: &'a str,
+    gui: &RiichiGui,
+    selected: Option<Kaze>,
+    exclude: Option<Kaze>,
+    on_select: fn(Kaze) -> Message,
+) -> Row<'a, Message> {
+    let mut seat_row = row![
+        text(label)
+            .width(Length::Fixed(140.0))
+            .horizontal_alignment(Horizontal::Right),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+    for (name, kaze) in [
+        ("East", Kaze::Ton),
+        ("South", Kaze::Nan),
+        ("West", Kaze::Shaa),
+        ("North", Kaze::Pei),
+    ] {
+        if (gui.is_sanma && kaze == Kaze::Pei) || exclude == Some(kaze) {
+            continue;
+        }
+        seat_row = seat_row.push(radio(name, kaze, selected, on_select));
+    }
+    seat_row
+}
@@ -162,21 +162,29 @@ pub fn build_melds_section(gui: &RiichiGui) -> Element<'_, Message> {
                 .collect::<Vec<Element<Message>>>()
         )
         .spacing(10),
-        row![
-            action_button(
+        {
+            let mut buttons = row![action_button(
                 "Add Pon",
                 Message::SelectMeldType(MentsuType::Koutsu),
                 ColoredButtonStyle::INFO,
-            ),
-            action_button(
-                "Add Chii",
-                Message::SelectMeldType(MentsuType::Shuntsu),
-                ColoredButtonStyle::INFO,
-            ),
-            action_button("Add Kan", Message::StartAddKan, ColoredButtonStyle::INFO,)
-        ]
-        .spacing(10)
-        .align_items(iced::Alignment::Center)
+            )];
+            // no chii in a three-player game
+            if !gui.is_sanma {
+                buttons = buttons.push(action_button(
+                    "Add Chii",
+                    Message::SelectMeldType(MentsuType::Shuntsu),
+                    ColoredButtonStyle::INFO,
+                ));
+            }
+            buttons
+                .push(action_button(
+                    "Add Kan",
+                    Message::StartAddKan,
+                    ColoredButtonStyle::INFO,
+                ))
+                .spacing(10)
+                .align_items(iced::Alignment::Center)
+        }
     ]
     .spacing(10)
     .align_items(iced::Alignment::Center)
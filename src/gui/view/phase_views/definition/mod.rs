@@ -13,6 +13,7 @@ use crate::gui::components::action_button;
 use crate::gui::messages::Message;
 use crate::gui::state::RiichiGui;
 use crate::gui::styles::ColoredButtonStyle;
+use crate::implements::game::AgariType;
 use iced::widget::{button, column, text};
 use iced::{Color, Element, theme};
 
@@ -20,9 +21,11 @@ pub fn build_definition_view(gui: &RiichiGui) -> Element<'_, Message> {
     let hand_preview = gui.view_hand_preview_locked();
     let modify_btn = action_button("Modify", Message::ModifyHand, ColoredButtonStyle::INFO);
 
+    let needs_discarder = gui.agari_type == AgariType::Ron && gui.discarder.is_none();
+
     let calculate_btn = button(text("Calculate Score"))
         .style(theme::Button::Custom(Box::new(ColoredButtonStyle::PRIMARY)))
-        .on_press_maybe(if gui.winning_tile.is_some() {
+        .on_press_maybe(if gui.winning_tile.is_some() && !needs_discarder {
             Some(Message::CalculateScore)
         } else {
             None
@@ -52,6 +55,14 @@ pub fn build_definition_view(gui: &RiichiGui) -> Element<'_, Message> {
         );
     }
 
+    if needs_discarder {
+        content = content.push(
+            text("You must select who discarded the winning tile")
+                .size(12)
+                .style(Color::from_rgb(0.8, 0.0, 0.0)),
+        );
+    }
+
     content
         .spacing(20)
         .align_items(iced::Alignment::Center)
@@ -2,12 +2,11 @@ use super::common::{checkbox_with_conflict, section_header};
 use crate::gui::messages::Message;
 use crate::gui::state::RiichiGui;
 use crate::implements::game::AgariType;
-use crate::implements::tiles::Kaze;
 use iced::widget::{column, container, row};
 use iced::{Element, Length};
 
 pub fn build_special_yaku_section(gui: &RiichiGui) -> Element<'_, Message> {
-    let is_oya = gui.jikaze == Kaze::Ton;
+    let is_oya = gui.jikaze == gui.oya_kaze;
     let is_ron = gui.agari_type == AgariType::Ron;
     let is_tsumo = gui.agari_type == AgariType::Tsumo;
     let is_menzen = gui.open_melds.is_empty();
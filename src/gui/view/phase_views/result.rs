@@ -1,8 +1,10 @@
 use crate::gui::messages::Message;
 use crate::gui::state::RiichiGui;
 use crate::gui::styles::ColoredButtonStyle;
+use crate::implements::error::AgariError;
 use crate::implements::game::AgariType;
 use crate::implements::scoring::{AgariResult, HandLimit};
+use crate::implements::tiles::Kaze;
 use crate::implements::yaku::Yaku;
 use iced::widget::{button, column, container, text};
 use iced::{Color, Element, Length, theme};
@@ -22,178 +24,212 @@ pub fn build_result_view(gui: &RiichiGui) -> Element<'_, Message> {
                 agari_type,
                 is_oya,
                 num_akadora,
+                num_nukidora,
+                kyoutaku,
+                point_deltas,
+                ..
             } = result;
 
-            let valid_yaku_count = yaku_list
-                .iter()
-                .filter(|y| !matches!(y, Yaku::Dora | Yaku::UraDora | Yaku::AkaDora))
-                .count();
-
-            if valid_yaku_count == 0 && limit_name.is_none() {
-                // Error message
-                column![
-                    text("No Yaku Found")
-                        .size(30)
-                        .style(Color::from_rgb(0.8, 0.0, 0.0))
-                        .font(iced::Font {
-                            weight: iced::font::Weight::Bold,
-                            ..iced::Font::with_name("Arimo")
-                        }),
-                    text("You need at least 1 Yaku to win.").size(20),
-                    text("(Dora does not count as Yaku)")
-                        .size(16)
-                        .style(Color::from_rgb(0.5, 0.5, 0.5))
-                ]
-                .spacing(15)
-                .align_items(iced::Alignment::Center)
-            } else {
-                // Success message
-                let hand_preview = gui.view_hand_preview_locked();
-                // Total Score
-                let score_text = text(format!("{} Points", total_payment))
-                    .size(40)
-                    .style(Color::from_rgb(0.8, 0.2, 0.2))
-                    .font(iced::Font {
-                        weight: iced::font::Weight::Bold,
-                        ..iced::Font::with_name("Arimo")
-                    });
-
-                // Limit Name
-                let limit_str = if let Some(limit) = limit_name {
-                    Some(match limit {
-                        HandLimit::Mangan => "Mangan",
-                        HandLimit::Haneman => "Haneman",
-                        HandLimit::Baiman => "Baiman",
-                        HandLimit::Sanbaiman => "Sanbaiman",
-                        HandLimit::Yakuman => {
-                            if *han >= 39 {
-                                "TRIPLE YAKUMAN!!!"
-                            } else if *han >= 26 {
-                                "DOUBLE YAKUMAN!!"
-                            } else {
-                                "YAKUMAN!"
-                            }
+            // Success message
+            let hand_preview = gui.view_hand_preview_locked();
+            // Total Score
+            let score_text = text(format!("{} Points", total_payment))
+                .size(40)
+                .style(Color::from_rgb(0.8, 0.2, 0.2))
+                .font(iced::Font {
+                    weight: iced::font::Weight::Bold,
+                    ..iced::Font::with_name("Arimo")
+                });
+
+            // Limit Name
+            let limit_str = if let Some(limit) = limit_name {
+                Some(match limit {
+                    HandLimit::Mangan => "Mangan",
+                    HandLimit::Haneman => "Haneman",
+                    HandLimit::Baiman => "Baiman",
+                    HandLimit::Sanbaiman => "Sanbaiman",
+                    HandLimit::Yakuman => {
+                        if *han >= 39 {
+                            "TRIPLE YAKUMAN!!!"
+                        } else if *han >= 26 {
+                            "DOUBLE YAKUMAN!!"
+                        } else {
+                            "YAKUMAN!"
                         }
-                    })
-                } else {
-                    None
-                };
-
-                // Han/Fu
-                let han_fu_text = if limit_name.as_ref() == Some(&HandLimit::Yakuman) {
-                    text(format!("{} Han", han)).size(20)
-                } else {
-                    text(format!("{} Han / {} Fu", han, fu)).size(20)
-                };
+                    }
+                })
+            } else {
+                None
+            };
 
-                // Yaku List
-                let mut yaku_col = column![];
-                let mut dora_count = 0;
-                let mut uradora_count = 0;
-
-                for yaku in yaku_list {
-                    match yaku {
-                        Yaku::Dora => dora_count += 1,
-                        Yaku::UraDora => uradora_count += 1,
-                        Yaku::AkaDora => {}
-                        _ => {
-                            yaku_col = yaku_col.push(text(format!("• {}", yaku)).size(18).font(
-                                iced::Font {
-                                    weight: iced::font::Weight::Bold,
-                                    ..iced::Font::with_name("Arimo")
-                                },
-                            ));
-                        }
+            // Han/Fu
+            let han_fu_text = if limit_name.as_ref() == Some(&HandLimit::Yakuman) {
+                text(format!("{} Han", han)).size(20)
+            } else {
+                text(format!("{} Han / {} Fu", han, fu)).size(20)
+            };
+
+            // Yaku List
+            let mut yaku_col = column![];
+            let mut dora_count = 0;
+            let mut uradora_count = 0;
+
+            for yaku in yaku_list {
+                match yaku {
+                    Yaku::Dora => dora_count += 1,
+                    Yaku::UraDora => uradora_count += 1,
+                    Yaku::AkaDora | Yaku::NukiDora => {}
+                    _ => {
+                        yaku_col =
+                            yaku_col.push(text(format!("• {}", yaku)).size(18).font(iced::Font {
+                                weight: iced::font::Weight::Bold,
+                                ..iced::Font::with_name("Arimo")
+                            }));
                     }
                 }
+            }
 
-                // dora
-                if dora_count > 0 {
-                    yaku_col = yaku_col.push(text(format!("• Dora x{}", dora_count)).size(18));
+            // dora
+            if dora_count > 0 {
+                yaku_col = yaku_col.push(text(format!("• Dora x{}", dora_count)).size(18));
+            }
+            if uradora_count > 0 {
+                yaku_col = yaku_col.push(text(format!("• Ura Dora x{}", uradora_count)).size(18));
+            }
+            if *num_akadora > 0 {
+                yaku_col = yaku_col.push(text(format!("• Red Dora x{}", num_akadora)).size(18));
+            }
+            if *num_nukidora > 0 {
+                yaku_col = yaku_col.push(text(format!("• Nuki Dora x{}", num_nukidora)).size(18));
+            }
+
+            // Payment
+            let rules = gui.rules();
+            let tsumo_bonus = *honba as u32 * rules.honba_tsumo_value;
+            let ron_bonus = *honba as u32 * rules.honba_value;
+
+            let payment_text = match (*is_oya, agari_type) {
+                (true, AgariType::Tsumo) => {
+                    format!(
+                        "Dealer Tsumo\nEach Non-Dealer pays: {} (+{} honba)",
+                        oya_payment, tsumo_bonus
+                    )
                 }
-                if uradora_count > 0 {
-                    yaku_col =
-                        yaku_col.push(text(format!("• Ura Dora x{}", uradora_count)).size(18));
+                (false, AgariType::Tsumo) => {
+                    // one other non-dealer in a three-player game
+                    let others = if gui.is_sanma {
+                        "Other Non-Dealer pays"
+                    } else {
+                        "Other Non-Dealers pay"
+                    };
+                    format!(
+                        "Non-Dealer Tsumo\nDealer pays: {} (+{} honba)\n{}: {} (+{} honba)",
+                        oya_payment, tsumo_bonus, others, ko_payment, tsumo_bonus
+                    )
                 }
-                if *num_akadora > 0 {
-                    yaku_col = yaku_col.push(text(format!("• Red Dora x{}", num_akadora)).size(18));
+                (true, AgariType::Ron) => {
+                    format!(
+                        "Dealer Ron\nDiscarder pays: {} (+{} honba)",
+                        total_payment - ron_bonus,
+                        ron_bonus
+                    )
                 }
-
-                // Payment
-                let tsumo_bonus = *honba as u32 * 100;
-                let ron_bonus = *honba as u32 * 300;
-
-                let payment_text = match (*is_oya, agari_type) {
-                    (true, AgariType::Tsumo) => {
-                        format!(
-                            "Dealer Tsumo\nEach Non-Dealer pays: {} (+{} honba)",
-                            oya_payment, tsumo_bonus
-                        )
-                    }
-                    (false, AgariType::Tsumo) => {
-                        format!(
-                            "Non-Dealer Tsumo\nDealer pays: {} (+{} honba)\nOther Non-Dealers pay: {} (+{} honba)",
-                            oya_payment, tsumo_bonus, ko_payment, tsumo_bonus
-                        )
-                    }
-                    (true, AgariType::Ron) => {
-                        format!(
-                            "Dealer Ron\nDiscarder pays: {} (+{} honba)",
-                            total_payment - ron_bonus,
-                            ron_bonus
-                        )
-                    }
-                    (false, AgariType::Ron) => {
-                        format!(
-                            "Non-Dealer Ron\nDiscarder pays: {} (+{} honba)",
-                            total_payment - ron_bonus,
-                            ron_bonus
-                        )
-                    }
+                (false, AgariType::Ron) => {
+                    format!(
+                        "Non-Dealer Ron\nDiscarder pays: {} (+{} honba)",
+                        total_payment - ron_bonus,
+                        ron_bonus
+                    )
+                }
+            };
+
+            let payment_section = container(text(payment_text).size(16).font(iced::Font {
+                weight: iced::font::Weight::Bold,
+                ..iced::Font::with_name("Arimo")
+            }))
+            .padding(10);
+
+            // Point exchange per seat
+            let mut exchange_col = column![
+                text(if *kyoutaku > 0 {
+                    format!("Point Exchange (incl. {} riichi sticks)", kyoutaku)
+                } else {
+                    "Point Exchange".to_string()
+                })
+                .size(16)
+            ]
+            .spacing(5)
+            .align_items(iced::Alignment::Center);
+            for (label, kaze) in [
+                ("East", Kaze::Ton),
+                ("South", Kaze::Nan),
+                ("West", Kaze::Shaa),
+                ("North", Kaze::Pei),
+            ] {
+                // the GUI always asks for the discarder, so the exchange is known
+                let Some(delta) = point_deltas.map(|deltas| deltas[kaze as usize]) else {
+                    continue;
                 };
-
-                let payment_section = container(text(payment_text).size(16).font(iced::Font {
-                    weight: iced::font::Weight::Bold,
-                    ..iced::Font::with_name("Arimo")
-                }))
-                .padding(10);
-
-                let mut result_column = column![
-                    hand_preview,
-                    iced::widget::Space::with_height(Length::Fixed(20.0)),
-                    score_text
-                ];
-
-                if let Some(limit) = limit_str {
-                    result_column = result_column.push(
-                        text(limit)
-                            .size(24)
-                            .style(Color::from_rgb(0.8, 0.0, 0.0))
-                            .font(iced::Font {
-                                weight: iced::font::Weight::Bold,
-                                ..iced::Font::with_name("Arimo")
-                            }),
-                    );
+                if gui.is_sanma && kaze == Kaze::Pei {
+                    continue;
                 }
+                let marker = if kaze == gui.jikaze { " (Winner)" } else { "" };
+                exchange_col =
+                    exchange_col.push(text(format!("{}{}: {:+}", label, marker, delta)).size(16));
+            }
 
-                result_column = result_column
-                    .push(han_fu_text)
-                    .push(yaku_col.spacing(5))
-                    .push(payment_section)
-                    .spacing(15)
-                    .align_items(iced::Alignment::Center);
+            let mut result_column = column![
+                hand_preview,
+                iced::widget::Space::with_height(Length::Fixed(20.0)),
+                score_text
+            ];
 
-                result_column
+            if let Some(limit) = limit_str {
+                result_column = result_column.push(
+                    text(limit)
+                        .size(24)
+                        .style(Color::from_rgb(0.8, 0.0, 0.0))
+                        .font(iced::Font {
+                            weight: iced::font::Weight::Bold,
+                            ..iced::Font::with_name("Arimo")
+                        }),
+                );
             }
+
+            result_column = result_column
+                .push(han_fu_text)
+                .push(yaku_col.spacing(5))
+                .push(payment_section)
+                .push(exchange_col)
+                .spacing(15)
+                .align_items(iced::Alignment::Center);
+
+            result_column
         }
-        Some(Err(_)) => column![
+        Some(Err(AgariError::NoYaku { .. })) => column![
             text("No Yaku Found")
                 .size(30)
                 .style(Color::from_rgb(0.8, 0.0, 0.0))
                 .font(iced::Font {
                     weight: iced::font::Weight::Bold,
                     ..iced::Font::with_name("Arimo")
-                })
+                }),
+            text("You need at least 1 Yaku to win.").size(20),
+            text("(Dora does not count as Yaku)")
+                .size(16)
+                .style(Color::from_rgb(0.5, 0.5, 0.5))
+        ]
+        .spacing(15)
+        .align_items(iced::Alignment::Center),
+        Some(Err(e)) => column![
+            text("Invalid Hand")
+                .size(30)
+                .style(Color::from_rgb(0.8, 0.0, 0.0))
+                .font(iced::Font {
+                    weight: iced::font::Weight::Bold,
+                    ..iced::Font::with_name("Arimo")
+                }),
+            text(e.to_string()).size(20)
         ]
         .spacing(15)
         .align_items(iced::Alignment::Center),
@@ -1,39 +1,38 @@
-use super::super::super::components::{cancel_button, create_grid, tile_button};
+use super::super::super::components::{cancel_button, tile_button};
 use super::super::super::messages::Message;
 use super::super::super::state::RiichiGui;
 use super::super::super::styles::ColoredButtonStyle;
 use iced::Element;
-use iced::widget::{column, text};
+use iced::widget::{column, row, text};
 
 impl RiichiGui {
     pub fn view_selecting_dora(&self, is_ura: bool) -> Element<'_, Message> {
-        iced::widget::lazy(is_ura, move |is_ura| {
-            let mut tiles = Vec::with_capacity(34);
+        iced::widget::lazy((is_ura, self.is_sanma), move |&(is_ura, _)| {
+            // one row per suit
+            let rows = self.tile_rows().into_iter().map(|tiles| {
+                let buttons = tiles.into_iter().map(|tile| {
+                    let handle = self
+                        .tile_images
+                        .get(&tile)
+                        .expect("Tile image not found")
+                        .clone();
 
-            for i in 0..34 {
-                let tile = crate::implements::tiles::index_to_tile(i);
-                let handle = self
-                    .tile_images
-                    .get(&tile)
-                    .expect("Tile image not found")
-                    .clone();
-
-                let btn = tile_button(
-                    iced::widget::Image::new(handle).width(40).into(),
-                    if *is_ura {
-                        Message::SelectUraDora(tile)
-                    } else {
-                        Message::SelectDora(tile)
-                    },
-                    ColoredButtonStyle::NEUTRAL_HOVER,
-                );
-
-                tiles.push(btn);
-            }
+                    tile_button(
+                        iced::widget::Image::new(handle).width(40).into(),
+                        if is_ura {
+                            Message::SelectUraDora(tile)
+                        } else {
+                            Message::SelectDora(tile)
+                        },
+                        ColoredButtonStyle::NEUTRAL_HOVER,
+                    )
+                });
+                row(buttons).spacing(10).into()
+            });
 
             let content: Element<'static, Message> = column![
                 text("Select").size(24),
-                create_grid(tiles, 9),
+                column(rows).spacing(10),
                 cancel_button()
             ]
             .spacing(20)
@@ -20,7 +20,7 @@ impl RiichiGui {
         let kan_buttons: Vec<Element<Message>> = possible_kans
             .iter()
             .map(|tile| {
-                let tiles = vec![*tile; 4];
+                let tiles = [*tile; 4];
                 let tile_images = row(tiles
                     .iter()
                     .enumerate()
@@ -54,7 +54,6 @@ impl RiichiGui {
                     Message::SelectOpenKan(*tile),
                     ColoredButtonStyle::NEUTRAL_HOVER,
                 )
-                .into()
             })
             .collect();
 
@@ -140,7 +139,6 @@ impl RiichiGui {
                     Message::SelectClosedKan(*tile),
                     ColoredButtonStyle::NEUTRAL_HOVER,
                 )
-                .into()
             })
             .collect();
 
@@ -181,7 +179,7 @@ impl RiichiGui {
                     && self
                         .hand_tiles
                         .iter()
-                        .filter(|&&t| t == m.representative_tile)
+                        .filter(|t| t.plain() == m.representative_tile)
                         .count()
                         >= 4
             })
@@ -201,7 +199,7 @@ impl RiichiGui {
             .iter()
             .map(|(idx, m)| {
                 let mut tiles = self.get_meld_tiles(m);
-                if tiles.len() > 0 {
+                if !tiles.is_empty() {
                     tiles.push(tiles[0]);
                 }
 
@@ -276,7 +274,6 @@ impl RiichiGui {
                     Message::SelectAddedKan(*idx),
                     ColoredButtonStyle::NEUTRAL_HOVER,
                 )
-                .into()
             })
             .collect();
 
@@ -56,7 +56,6 @@ impl RiichiGui {
                             Message::SelectCompleteMeld(meld.clone()),
                             ColoredButtonStyle::NEUTRAL_HOVER,
                         )
-                        .into()
                     })
                     .collect();
 
@@ -1,8 +1,9 @@
 use super::super::messages::Message;
 
-use super::super::components::{action_button, tile_image, tile_image_button};
+use super::super::components::{RED_FIVES, action_button, tile_image, tile_image_button};
 use super::super::state::RiichiGui;
 use super::super::styles::ColoredButtonStyle;
+use crate::implements::tiles::Hai;
 use iced::widget::{button, column, container, image, row, text};
 use iced::{Color, Element, Length, theme};
 
@@ -39,8 +40,7 @@ impl RiichiGui {
         let tiles: Vec<Element<Message>> = self
             .hand_tiles
             .iter()
-            .enumerate()
-            .map(|(_, tile)| {
+            .map(|tile| {
                 let handle = self
                     .tile_images
                     .get(tile)
@@ -53,55 +53,66 @@ impl RiichiGui {
         row(tiles).spacing(5).into()
     }
 
-    /// in composition phase
+    /// in composition phase; each suit row ends with its red five
     pub fn view_tile_pool(&self) -> Element<'_, Message> {
-        let mut tiles = Vec::new();
+        let rows = self
+            .tile_rows()
+            .into_iter()
+            .enumerate()
+            .map(|(suit, mut tiles)| {
+                // the three-player set has no red 5m
+                if let Some(&red) = RED_FIVES.get(suit)
+                    && tiles.contains(&red.plain())
+                {
+                    tiles.push(red);
+                }
+                row(tiles.into_iter().map(|tile| self.view_pool_tile(tile)))
+                    .spacing(10)
+                    .into()
+            });
 
-        for i in 0..34 {
-            let tile = crate::implements::tiles::index_to_tile(i);
-            let count = self.tile_counts[i];
-            let handle = self
-                .tile_images
-                .get(&tile)
-                .expect("Tile image not found")
-                .clone();
+        column(rows).spacing(10).into()
+    }
 
-            let tile_image = image(handle).width(50);
+    fn view_pool_tile(&self, tile: Hai) -> Element<'_, Message> {
+        let count = self.pool_count(&tile);
+        let handle = self
+            .tile_images
+            .get(&tile)
+            .expect("Tile image not found")
+            .clone();
 
-            let count_text = text(format!("({})", count)).size(12).style(if count > 0 {
-                Color::BLACK
-            } else {
-                Color::from_rgb(0.5, 0.5, 0.5)
-            });
+        let tile_image = image(handle).width(50);
 
-            let button_bg_color = if count > 0 {
-                Color::WHITE
-            } else {
-                Color::from_rgb(0.85, 0.85, 0.85)
-            };
+        let count_text = text(format!("({})", count)).size(12).style(if count > 0 {
+            Color::BLACK
+        } else {
+            Color::from_rgb(0.5, 0.5, 0.5)
+        });
 
-            let btn = button(column![tile_image, count_text].align_items(iced::Alignment::Center))
-                .style(theme::Button::Custom(Box::new(ColoredButtonStyle {
-                    background_color: button_bg_color,
-                    text_color: Color::BLACK,
-                    hover_color: if count > 0 {
-                        Some(Color::from_rgb(0.8, 0.8, 0.8))
-                    } else {
-                        None
-                    },
-                })))
-                .on_press_maybe(if count > 0 {
-                    Some(Message::AddTile(tile))
+        let button_bg_color = if count > 0 {
+            Color::WHITE
+        } else {
+            Color::from_rgb(0.85, 0.85, 0.85)
+        };
+
+        button(column![tile_image, count_text].align_items(iced::Alignment::Center))
+            .style(theme::Button::Custom(Box::new(ColoredButtonStyle {
+                background_color: button_bg_color,
+                text_color: Color::BLACK,
+                hover_color: if count > 0 {
+                    Some(Color::from_rgb(0.8, 0.8, 0.8))
                 } else {
                     None
-                })
-                .padding(5)
-                .into();
-
-            tiles.push(btn);
-        }
-
-        super::super::components::create_grid(tiles, 9)
+                },
+            })))
+            .on_press_maybe(if count > 0 {
+                Some(Message::AddTile(tile))
+            } else {
+                None
+            })
+            .padding(5)
+            .into()
     }
 
     /// every phase
@@ -1,16 +1,50 @@
 pub mod types;
 pub use types::*;
 pub mod raw_hand_organizer;
-pub use raw_hand_organizer::*;
+pub use raw_hand_organizer::organize_hand;
 pub mod yaku_checkers;
-pub use yaku_checkers::*;
+pub use yaku_checkers::{YakuResult, check_all_yaku};
 pub mod score_calculator;
-pub use score_calculator::*;
+pub use score_calculator::calculate_score;
+pub mod notation;
+pub use notation::parse_hand;
+#[cfg(feature = "replay")]
+pub mod replay;
 
+use crate::implements::error::AgariError;
+use crate::implements::game::AgariType;
 use crate::implements::input::UserInput;
+use crate::implements::rules::RuleSet;
 use crate::implements::scoring::AgariResult;
+use crate::implements::tiles::{TILE_COUNT, index_to_tile};
+use crate::implements::yaku::Yaku;
+
+// best interpretation of the hand
+pub fn calculate_agari(input: &UserInput, rules: &RuleSet) -> Result<AgariResult, AgariError> {
+    let results = calculate_agari_all(input, rules)?;
+    Ok(results
+        .into_iter()
+        .next()
+        .expect("calculate_agari_all returns at least one result"))
+}
+
+// every scored interpretation, best first (points, then han, then fu)
+pub fn calculate_agari_all(
+    input: &UserInput,
+    rules: &RuleSet,
+) -> Result<Vec<AgariResult>, AgariError> {
+    // rules may come from anywhere, not only a rule file
+    rules.validate().map_err(AgariError::InvalidRules)?;
+
+    // validate input
+    input.validate()?;
+    input.validate_akadora(&rules.red_fives)?;
+    input.validate_players(rules.sanma)?;
+
+    // red fives only add dora; the hand is read with plain tiles
+    let num_akadora = input.num_akadora()?;
+    let input = &input.without_red_fives().with_derived_flags();
 
-pub fn calculate_agari(input: &UserInput) -> Result<AgariResult, &'static str> {
     // receive input
     let player = &input.player_context;
     let game = &input.game_context;
@@ -20,23 +54,235 @@ pub fn calculate_agari(input: &UserInput) -> Result<AgariResult, &'static str> {
     let organizations = organize_hand(input)?;
 
     // check yaku
-    let mut best_result: Option<AgariResult> = None;
-    let mut best_payment = 0;
+    let mut results: Vec<AgariResult> = Vec::new();
+    let mut last_error: Option<AgariError> = None;
 
     for organization in organizations {
-        if let Ok(yaku_result) = check_all_yaku(organization, player, game, agari_type) {
-            let final_score = calculate_score(yaku_result, player, game, agari_type);
+        match check_all_yaku(organization, player, game, agari_type, num_akadora, rules) {
+            Ok(yaku_result) => {
+                results.push(calculate_score(
+                    yaku_result,
+                    player,
+                    game,
+                    agari_type,
+                    rules,
+                ));
+            }
+            // a rule restriction outranks no yaku, which outranks an incomplete reading
+            Err(e) => {
+                if error_rank(&e) >= last_error.as_ref().map_or(0, error_rank) {
+                    last_error = Some(e);
+                }
+            }
+        }
+    }
+
+    // 後付け: without atozuke, every wait needs a yaku
+    if !results.is_empty() && !rules.atozuke && has_wait_without_yaku(input, rules) {
+        return Err(AgariError::RestrictedByRules {
+            rule: "atozuke",
+            han: 0,
+            required: 1,
+        });
+    }
+
+    // 二飜縛り: 2 han without dora from the set honba on
+    if let Some(from_honba) = rules.ryanhan_shibari
+        && game.honba >= from_honba
+        && let Some(best_han) = results.iter().map(han_without_dora).max()
+        && best_han < 2
+    {
+        return Err(AgariError::RestrictedByRules {
+            rule: "ryanhan shibari",
+            han: best_han,
+            required: 2,
+        });
+    }
+
+    // rank results
+    if !results.is_empty() {
+        results.sort_by_key(|r| std::cmp::Reverse((r.total_payment, r.han, r.fu)));
+        return Ok(results);
+    }
+
+    Err(last_error.unwrap_or_else(|| AgariError::IncompleteHand {
+        tiles: input.hand_tiles.clone(),
+    }))
+}
+
+fn error_rank(err: &AgariError) -> u8 {
+    match err {
+        AgariError::RestrictedByRules { .. } => 2,
+        AgariError::NoYaku { .. } => 1,
+        _ => 0,
+    }
+}
+
+// han from yaku alone
+fn han_without_dora(result: &AgariResult) -> u8 {
+    let dora = result
+        .yaku_list
+        .iter()
+        .filter(|yaku| {
+            matches!(
+                yaku,
+                Yaku::Dora | Yaku::UraDora | Yaku::AkaDora | Yaku::NukiDora
+            )
+        })
+        .count() as u8;
+    result.han.saturating_sub(dora)
+}
+
+// some other tile completes the tenpai hand without a yaku
+fn has_wait_without_yaku(input: &UserInput, rules: &RuleSet) -> bool {
+    let relaxed = RuleSet {
+        atozuke: true,
+        ryanhan_shibari: None,
+        ..rules.clone()
+    };
+    let mut tenpai_hand = input.hand_tiles.clone();
+    if input.agari_type == AgariType::Tsumo
+        && let Some(pos) = tenpai_hand.iter().position(|t| *t == input.winning_tile)
+    {
+        tenpai_hand.remove(pos);
+    }
 
-            if final_score.total_payment >= best_payment {
-                best_payment = final_score.total_payment;
-                best_result = Some(final_score);
+    (0..TILE_COUNT)
+        .map(index_to_tile)
+        .filter(|wait| *wait != input.winning_tile)
+        .any(|wait| {
+            let mut alt = input.clone();
+            alt.winning_tile = wait;
+            alt.hand_tiles = tenpai_hand.clone();
+            if alt.agari_type == AgariType::Tsumo {
+                alt.hand_tiles.push(wait);
             }
+            // waits that do not complete the hand are skipped
+            matches!(
+                calculate_agari_all(&alt, &relaxed),
+                Err(AgariError::NoYaku { .. } | AgariError::RestrictedByRules { .. })
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{calculate_agari, calculate_agari_all};
+    use crate::implements::notation::parse_hand;
+    use crate::implements::types::{
+        error::AgariError,
+        hand::HandStructure,
+        input::UserInput,
+        rules::RuleSet,
+        tiles::{Hai, Kaze},
+        yaku::Yaku,
+    };
+
+    fn ron(text: &str) -> UserInput {
+        parse_hand(text)
+            .unwrap()
+            .seat(Kaze::Nan)
+            .ron(Kaze::Shaa)
+            .build()
+            .unwrap()
+    }
+
+    fn restricted(rule: &'static str, han: u8) -> AgariError {
+        AgariError::RestrictedByRules {
+            rule,
+            han,
+            required: if rule == "ryanhan shibari" { 2 } else { 1 },
         }
     }
 
-    // return result
-    match best_result {
-        Some(res) => Ok(res),
-        None => Err("No valid Yaku found"),
+    // 二盃口 mangan outranks the same tiles read as 七対子
+    #[test]
+    fn interpretations_are_ranked_by_payment() {
+        let input = ron("223344m556677p8s 8s");
+        let results = calculate_agari_all(&input, &RuleSet::default()).unwrap();
+        let ranked: Vec<(bool, u32, u8, u8)> = results
+            .iter()
+            .map(|result| {
+                let chiitoitsu = matches!(result.hand_structure, HandStructure::Chiitoitsu { .. });
+                (chiitoitsu, result.total_payment, result.han, result.fu)
+            })
+            .collect();
+        assert_eq!(ranked, [(false, 8000, 4, 40), (true, 3200, 3, 25)]);
+        assert!(results[0].yaku_list.contains(&Yaku::Ryanpeikou));
+
+        let best = calculate_agari(&input, &RuleSet::default()).unwrap();
+        assert!(best.yaku_list.contains(&Yaku::Ryanpeikou));
+        assert_eq!(best.total_payment, results[0].total_payment);
+    }
+
+    // a RuleSet that never went through a rule file is still checked
+    #[test]
+    fn invalid_rules_are_rejected() {
+        let input = ron("234m567p345s99s78m 6m");
+        let rules = RuleSet {
+            chiitoitsu_fu: 0,
+            ..RuleSet::default()
+        };
+        assert!(matches!(
+            calculate_agari(&input, &rules),
+            Err(AgariError::InvalidRules(err)) if err.context == "chiitoitsu_fu"
+        ));
+        let rules = RuleSet {
+            red_fives: [9, 0, 0],
+            ..RuleSet::default()
+        };
+        assert!(matches!(
+            calculate_agari(&input, &rules),
+            Err(AgariError::InvalidRules(err)) if err.context == "red_fives"
+        ));
+    }
+
+    // 後付け: 678 sanshoku on 8p, nothing on 5p
+    #[test]
+    fn atozuke_off_needs_a_yaku_on_every_wait() {
+        let rules = RuleSet {
+            atozuke: false,
+            ..RuleSet::default()
+        };
+        let sanshoku = ron("678m67p234p11m 678s+ 8p");
+        assert_eq!(
+            calculate_agari(&sanshoku, &rules).unwrap_err(),
+            restricted("atozuke", 0)
+        );
+        let result = calculate_agari(&sanshoku, &RuleSet::default()).unwrap();
+        assert_eq!(result.yaku_list, [Yaku::SanshokuDoujun]);
+
+        // open tanyao on either wait
+        let tanyao = ron("678m67p234p55m 678s+ 8p");
+        assert!(calculate_agari(&tanyao, &rules).is_ok());
+    }
+
+    // 二飜縛り: dora do not count toward the two han
+    #[test]
+    fn ryanhan_shibari_counts_han_without_dora() {
+        let rules = RuleSet {
+            ryanhan_shibari: Some(5),
+            ..RuleSet::default()
+        };
+        let pinfu = || {
+            parse_hand("234m567p345s99s78m 6m")
+                .unwrap()
+                .seat(Kaze::Nan)
+                .ron(Kaze::Shaa)
+                .dora(["8s".parse::<Hai>().unwrap()])
+        };
+
+        // pinfu and the 99s pair as dora, at 5 honba
+        let dora_only = pinfu().honba(5).build().unwrap();
+        assert_eq!(
+            calculate_agari(&dora_only, &rules).unwrap_err(),
+            restricted("ryanhan shibari", 1)
+        );
+        // below the threshold one han is enough
+        let early = pinfu().honba(4).build().unwrap();
+        assert_eq!(calculate_agari(&early, &rules).unwrap().han, 3);
+        // riichi makes it two han from yaku
+        let riichi = pinfu().honba(5).riichi().build().unwrap();
+        assert_eq!(calculate_agari(&riichi, &rules).unwrap().han, 4);
     }
 }
@@ -0,0 +1,311 @@
+// MPSZ hand notation
+//
+//   234m567p22s 345s+ 5s
+//
+// - tiles are digits followed by their suit (m, p, s, z); 0 is a red five
+// - called melds end in a suffix: + chii/pon/daiminkan (by shape),
+//   * shouminkan (added kan), # ankan (closed kan)
+// - the last tile written outside the melds is the winning tile
+
+use crate::implements::types::{
+    builder::UserInputBuilder,
+    error::ParseHandError,
+    game::AgariType,
+    hand::{AgariHand, MentsuType},
+    input::UserInput,
+    tiles::{Hai, index_to_tile, tile_to_index},
+};
+use std::fmt;
+
+// read a hand into a builder; game context is set on the builder
+pub fn parse_hand(notation: &str) -> Result<UserInputBuilder, ParseHandError> {
+    let mut builder = UserInput::builder();
+    let mut concealed: Vec<Hai> = Vec::new();
+
+    for token in notation.split_whitespace() {
+        let (body, suffix) = match token.char_indices().last() {
+            Some((i, c @ ('+' | '*' | '#'))) => (&token[..i], Some(c)),
+            _ => (token, None),
+        };
+        let tiles = parse_tiles(body).map_err(|reason| ParseHandError {
+            token: token.to_string(),
+            reason,
+        })?;
+
+        match suffix {
+            None => concealed.extend(tiles),
+            Some(suffix) => {
+                builder = add_meld(builder, &tiles, suffix).ok_or_else(|| ParseHandError {
+                    token: token.to_string(),
+                    reason: "not a meld of this kind",
+                })?;
+            }
+        }
+    }
+
+    let winning_tile = concealed.pop().ok_or_else(|| ParseHandError {
+        token: notation.to_string(),
+        reason: "no winning tile",
+    })?;
+
+    Ok(builder.hand(concealed).win_on(winning_tile))
+}
+
+// digits then suit, e.g. "123m0p11z"
+fn parse_tiles(body: &str) -> Result<Vec<Hai>, &'static str> {
+    let mut tiles = Vec::new();
+    let mut digits: Vec<char> = Vec::new();
+
+    for c in body.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        if digits.is_empty() {
+            return Err("suit without numbers");
+        }
+        for digit in digits.drain(..) {
+            let text: String = [digit, c].iter().collect();
+            let tile: Hai = text.parse().map_err(|_| "unknown tile")?;
+            tiles.push(tile);
+        }
+    }
+
+    if !digits.is_empty() {
+        return Err("numbers without a suit");
+    }
+    if tiles.is_empty() {
+        return Err("no tiles");
+    }
+    Ok(tiles)
+}
+
+// red fives are counted onto the meld
+fn add_meld(builder: UserInputBuilder, tiles: &[Hai], suffix: char) -> Option<UserInputBuilder> {
+    let first = tiles.first()?.plain();
+    let all_same = tiles.iter().all(|tile| tile.plain() == first);
+    let reds = tiles.iter().filter(|tile| tile.is_red()).count() as u8;
+
+    let builder = match (suffix, tiles.len()) {
+        ('+', 3) if all_same => Some(builder.pon(first)),
+        ('+', 3) => {
+            let mut indices: Vec<usize> = tiles.iter().map(tile_to_index).collect();
+            indices.sort_unstable();
+            let start = index_to_tile(indices[0]);
+            let is_sequence = matches!(start, Hai::Suhai(_))
+                && indices[1] == indices[0] + 1
+                && indices[2] == indices[0] + 2
+                && same_suit(&index_to_tile(indices[2]), &start);
+            is_sequence.then(|| builder.chii(start))
+        }
+        ('+', 4) if all_same => Some(builder.open_kan(first)),
+        ('*', 4) if all_same => Some(builder.added_kan(first)),
+        ('#', 4) if all_same => Some(builder.closed_kan(first)),
+        _ => None,
+    }?;
+    Some(builder.meld_akadora(reds))
+}
+
+fn same_suit(a: &Hai, b: &Hai) -> bool {
+    match (a, b) {
+        (Hai::Suhai(x), Hai::Suhai(y)) => x.suit == y.suit,
+        _ => false,
+    }
+}
+
+// tiles grouped by suit, e.g. "234m55p"
+fn format_tiles(tiles: &[Hai]) -> String {
+    let mut out = String::new();
+    let mut current_suit: Option<char> = None;
+
+    for tile in tiles {
+        let text = tile.to_string();
+        let (digit, suit) = text.split_at(1);
+        let suit = suit.chars().next().unwrap_or('z');
+
+        if let Some(prev) = current_suit
+            && prev != suit
+        {
+            out.push(prev);
+        }
+        out.push_str(digit);
+        current_suit = Some(suit);
+    }
+    if let Some(suit) = current_suit {
+        out.push(suit);
+    }
+    out
+}
+
+fn sorted(mut tiles: Vec<Hai>) -> Vec<Hai> {
+    tiles.sort_by_key(tile_to_index);
+    tiles
+}
+
+// UserInput back to MPSZ notation
+//
+// Display never fails: a meld that cannot be expanded (a chii that runs past
+// 9, more red fives than fives) is written as its tile repeated.
+impl fmt::Display for UserInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut tokens = Vec::new();
+
+        // Tsumo hands carry the winning tile in hand_tiles
+        let mut concealed = self.hand_tiles.clone();
+        if self.agari_type == AgariType::Tsumo
+            && let Some(pos) = concealed.iter().position(|t| *t == self.winning_tile)
+        {
+            concealed.remove(pos);
+        }
+        if !concealed.is_empty() {
+            tokens.push(format_tiles(&sorted(concealed)));
+        }
+
+        for meld in &self.open_melds {
+            let suffix = if meld.is_added_kan { '*' } else { '+' };
+            let count = match meld.mentsu_type {
+                MentsuType::Kantsu => 4,
+                _ => 3,
+            };
+            let tiles = meld
+                .tiles()
+                .unwrap_or_else(|_| vec![meld.representative_tile; count]);
+            tokens.push(format!("{}{}", format_tiles(&tiles), suffix));
+        }
+        for kan in &self.closed_kans {
+            let tiles = kan.tiles().unwrap_or_else(|_| vec![kan.tile; 4]);
+            tokens.push(format!("{}#", format_tiles(&tiles)));
+        }
+
+        tokens.push(format_tiles(&[self.winning_tile]));
+        write!(f, "{}", tokens.join(" "))
+    }
+}
+
+// AgariHand in the same notation: concealed tiles, calls, winning tile
+//
+// Sets completed by ron count as open for fu but were never called, so they
+// stay with the concealed tiles. Called kans are written with +.
+impl fmt::Display for AgariHand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut concealed = vec![self.atama.0, self.atama.1];
+        let mut calls = Vec::new();
+        let mut closed_kans = Vec::new();
+
+        for mentsu in &self.mentsu {
+            let tiles = mentsu.meld_tiles();
+            match (mentsu.mentsu_type, mentsu.is_called) {
+                (_, true) => calls.push(format!("{}+", format_tiles(tiles))),
+                (MentsuType::Kantsu, false) => {
+                    closed_kans.push(format!("{}#", format_tiles(tiles)))
+                }
+                (_, false) => concealed.extend_from_slice(tiles),
+            }
+        }
+        if let Some(pos) = concealed.iter().position(|t| *t == self.agari_hai) {
+            concealed.remove(pos);
+        }
+
+        let mut tokens = vec![format_tiles(&sorted(concealed))];
+        tokens.extend(calls);
+        tokens.extend(closed_kans);
+        tokens.push(format_tiles(&[self.agari_hai]));
+        write!(f, "{}", tokens.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_hand;
+    use crate::implements::organize_hand;
+    use crate::implements::types::{
+        game::AgariType,
+        hand::{HandOrganization, MentsuType},
+        input::{OpenMeldInput, UserInput},
+        tiles::{Kaze, tile_to_index},
+    };
+
+    fn ron(notation: &str) -> UserInput {
+        parse_hand(notation)
+            .unwrap()
+            .ron(Kaze::Nan)
+            .build()
+            .unwrap()
+    }
+
+    fn tsumo(notation: &str) -> UserInput {
+        parse_hand(notation).unwrap().tsumo().build().unwrap()
+    }
+
+    // same tiles, melds and winning tile
+    fn assert_same_hand(a: &UserInput, b: &UserInput) {
+        let sorted = |input: &UserInput| {
+            let mut tiles = input.hand_tiles.clone();
+            tiles.sort_by_key(tile_to_index);
+            tiles
+        };
+        assert_eq!(sorted(a), sorted(b));
+        assert_eq!(a.winning_tile, b.winning_tile);
+        assert_eq!(a.open_melds, b.open_melds);
+        assert_eq!(a.closed_kans, b.closed_kans);
+    }
+
+    #[test]
+    fn parse_format_parse_round_trip() {
+        let hands = [
+            ron("234m406p2245s 345s+ 6s"),
+            ron("123m456p789s11z22z 2z"),
+            tsumo("1m 0555p+ 6666s* 7777z# 123m 1m"),
+            tsumo("22m06p345m 678s+ 5550s+ 4p"),
+        ];
+        for input in hands {
+            let text = input.to_string();
+            let reparsed = if input.agari_type == AgariType::Tsumo {
+                tsumo(&text)
+            } else {
+                ron(&text)
+            };
+            assert_same_hand(&input, &reparsed);
+            assert_eq!(reparsed.to_string(), text);
+        }
+    }
+
+    #[test]
+    fn display_never_fails() {
+        // a chii starting on 8m cannot be expanded
+        let mut input = ron("123m456p789s11z22z 2z");
+        input.hand_tiles.truncate(input.hand_tiles.len() - 3);
+        input.open_melds.push(OpenMeldInput {
+            mentsu_type: MentsuType::Shuntsu,
+            representative_tile: "8m".parse().unwrap(),
+            is_added_kan: false,
+            num_akadora: 0,
+        });
+        assert_eq!(input.to_string(), "123m456p789s1z 888m+ 2z");
+    }
+
+    #[test]
+    fn agari_hand_round_trip() {
+        let hands = [
+            // ron completes the concealed 222z: written without +
+            ron("123m456p789s11z22z 2z"),
+            ron("234m67p22s 678s+ 345s+ 5p"),
+            tsumo("1m 555p+ 6666s+ 7777z# 123m 1m"),
+        ];
+        for input in hands {
+            let organizations = organize_hand(&input).unwrap();
+            for organization in organizations {
+                let HandOrganization::YonmentsuIchiatama(hand) = organization else {
+                    continue;
+                };
+                let text = hand.to_string();
+                let reparsed = if input.agari_type == AgariType::Tsumo {
+                    tsumo(&text)
+                } else {
+                    ron(&text)
+                };
+                assert_same_hand(&input, &reparsed);
+            }
+        }
+    }
+}
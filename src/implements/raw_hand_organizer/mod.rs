@@ -3,20 +3,36 @@ pub mod wait_analyzer;
 
 use self::{recursive_parser::find_all_mentsu_recursive, wait_analyzer::determine_wait_type};
 use crate::implements::types::{
+    error::AgariError,
     game::AgariType,
     hand::{AgariHand, HandOrganization, Machi, Mentsu, MentsuType},
     input::UserInput,
-    tiles::{index_to_tile, tile_to_index},
+    tiles::{counts_to_tiles, index_to_tile, tile_to_index},
 };
 use std::convert::TryInto;
 
-pub fn organize_hand(input: &UserInput) -> Result<Vec<HandOrganization>, &'static str> {
+pub fn organize_hand(input: &UserInput) -> Result<Vec<HandOrganization>, AgariError> {
+    let is_ron = input.agari_type == AgariType::Ron;
+
+    // meld count check
+    let meld_count = input.open_melds.len() + input.closed_kans.len();
+    if meld_count > 4 {
+        return Err(AgariError::TooManyMelds { count: meld_count });
+    }
+
+    // tile count check, before counting into u8
+    let expected = 14 - 3 * meld_count;
+    let found = input.hand_tiles.len() + is_ron as usize;
+    if found != expected {
+        return Err(AgariError::WrongTileCount { expected, found });
+    }
+
     let mut master_counts = [0u8; 34];
     for tile in &input.hand_tiles {
         master_counts[tile_to_index(tile)] += 1;
     }
 
-    if input.agari_type == AgariType::Ron {
+    if is_ron {
         master_counts[tile_to_index(&input.winning_tile)] += 1;
     }
 
@@ -25,18 +41,19 @@ pub fn organize_hand(input: &UserInput) -> Result<Vec<HandOrganization>, &'stati
     let mut open_mentsu: Vec<Mentsu> = Vec::with_capacity(4);
 
     // Closed Kans
-    for rep_tile in &input.closed_kans {
-        let kan_tile = *rep_tile;
+    for kan in &input.closed_kans {
+        let kan_tile = kan.tile.plain();
         open_mentsu.push(Mentsu {
             mentsu_type: MentsuType::Kantsu,
             is_minchou: false,
+            is_called: false,
             tiles: [kan_tile, kan_tile, kan_tile, kan_tile],
         });
     }
 
     // Open Melds
     for meld in &input.open_melds {
-        let rep_tile = meld.representative_tile;
+        let rep_tile = meld.representative_tile.plain();
         let index = tile_to_index(&rep_tile);
 
         match meld.mentsu_type {
@@ -44,6 +61,7 @@ pub fn organize_hand(input: &UserInput) -> Result<Vec<HandOrganization>, &'stati
                 open_mentsu.push(Mentsu {
                     mentsu_type: MentsuType::Koutsu,
                     is_minchou: true,
+                    is_called: true,
                     tiles: [rep_tile, rep_tile, rep_tile, rep_tile],
                 });
             }
@@ -51,6 +69,7 @@ pub fn organize_hand(input: &UserInput) -> Result<Vec<HandOrganization>, &'stati
                 open_mentsu.push(Mentsu {
                     mentsu_type: MentsuType::Kantsu,
                     is_minchou: true,
+                    is_called: true,
                     tiles: [rep_tile, rep_tile, rep_tile, rep_tile],
                 });
             }
@@ -60,7 +79,7 @@ pub fn organize_hand(input: &UserInput) -> Result<Vec<HandOrganization>, &'stati
                 let index3 = index1 + 2;
 
                 if index1 >= 27 || (index1 % 9) >= 7 {
-                    return Err("Invalid representative tile for Chi (must be 1-7)");
+                    return Err(AgariError::InvalidChiiStart { tile: rep_tile });
                 }
 
                 let t1 = rep_tile;
@@ -69,6 +88,7 @@ pub fn organize_hand(input: &UserInput) -> Result<Vec<HandOrganization>, &'stati
                 open_mentsu.push(Mentsu {
                     mentsu_type: MentsuType::Shuntsu,
                     is_minchou: true,
+                    is_called: true,
                     tiles: [t1, t2, t3, t3],
                 });
             }
@@ -80,15 +100,18 @@ pub fn organize_hand(input: &UserInput) -> Result<Vec<HandOrganization>, &'stati
 
     // 4 known melds
     if mentsu_needed == 0 {
-        for i in 0..34 {
-            if concealed_counts[i] == 2 {
+        for (i, &count) in concealed_counts.iter().enumerate() {
+            if count == 2 {
                 let pair_tile = index_to_tile(i);
                 let atama = (pair_tile, pair_tile);
 
-                let mentsu_array: [Mentsu; 4] = open_mentsu
-                    .clone()
-                    .try_into()
-                    .map_err(|_| "final_mentsu length not 4")?;
+                let mentsu_array: [Mentsu; 4] =
+                    open_mentsu
+                        .clone()
+                        .try_into()
+                        .map_err(|_| AgariError::TooManyMelds {
+                            count: open_mentsu.len(),
+                        })?;
 
                 let agari_hand = AgariHand {
                     mentsu: mentsu_array,
@@ -100,9 +123,10 @@ pub fn organize_hand(input: &UserInput) -> Result<Vec<HandOrganization>, &'stati
                 final_results.push(HandOrganization::YonmentsuIchiatama(agari_hand));
             }
         }
-        if input.hand_tiles.len() == 14 {
-        } else if final_results.is_empty() {
-            return Err("4 open melds but no pair found");
+        if final_results.is_empty() {
+            return Err(AgariError::IncompleteHand {
+                tiles: counts_to_tiles(&concealed_counts),
+            });
         }
     } else {
         // Standard Hand
@@ -129,10 +153,8 @@ pub fn organize_hand(input: &UserInput) -> Result<Vec<HandOrganization>, &'stati
                                 for (machi, index) in possible_waits {
                                     let mut final_mentsu = mentsu_array;
 
-                                    if input.agari_type == AgariType::Ron {
-                                        if index < 4 {
-                                            final_mentsu[index].is_minchou = true;
-                                        }
+                                    if input.agari_type == AgariType::Ron && index < 4 {
+                                        final_mentsu[index].is_minchou = true;
                                     }
 
                                     let agari_hand = AgariHand {
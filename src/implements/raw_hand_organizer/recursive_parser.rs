@@ -26,6 +26,7 @@ pub fn find_all_mentsu_recursive<F>(
         mentsu.push(Mentsu {
             mentsu_type: MentsuType::Koutsu,
             is_minchou: false,
+            is_called: false,
             tiles: [tile, tile, tile, tile],
         });
 
@@ -52,6 +53,7 @@ pub fn find_all_mentsu_recursive<F>(
         mentsu.push(Mentsu {
             mentsu_type: MentsuType::Shuntsu,
             is_minchou: false,
+            is_called: false,
             tiles: [tile1, tile2, tile3, tile3],
         });
 
@@ -44,7 +44,7 @@ pub fn determine_wait_type(
                         Machi::Ryanmen
                     }
                 } else if agari_hai == t3 {
-                    if tile_to_index(&t1) % TILES_PER_SUHAI == 0 {
+                    if tile_to_index(&t1).is_multiple_of(TILES_PER_SUHAI) {
                         Machi::Penchan
                     } else {
                         Machi::Ryanmen
@@ -0,0 +1,311 @@
+// Mahjong Soul (雀魂) paipu import, from the decoded JSON record list
+//
+//   {"name": ".lq.RecordNewRound", "data": {"chang": 0, "ju": 1, "ben": 0, "liqibang": 0, ...}}
+//   {"name": ".lq.RecordDiscardTile", "data": {"seat": 2, "tile": "3s", ...}}
+//   {"name": ".lq.RecordHule", "data": {"hules": [HuleInfo, ...], "delta_scores": [...]}}
+//
+// Each HuleInfo carries the winning hand itself (hand, ming, hu_tile), every
+// dora indicator including kan dora (doras, li_doras) and the recorded score
+// (fans, fu, point_rong / point_zimo_qin / point_zimo_xian). Tiles are MPSZ
+// strings with 0 for a red five. Seats are fixed; ju is the dealer's seat.
+//
+// Records are accepted as a plain list, or nested the way the common
+// decoders write them ({"data": {"data": {"records": [...]}}}, or the newer
+// {"actions": [{"result": {...}}]}).
+
+use super::{ExpectedScore, RecordedAgari, round_label, seat_of};
+use crate::implements::types::{
+    builder::UserInputBuilder,
+    error::ReplayError,
+    input::UserInput,
+    tiles::{Hai, tile_to_index},
+};
+use serde_json::Value;
+
+// fan ids of the yaku that come from the game state
+const FAN_RIICHI: u64 = 2;
+const FAN_CHANKAN: u64 = 3;
+const FAN_RINSHAN: u64 = 4;
+const FAN_HAITEI: u64 = 5;
+const FAN_HOUTEI: u64 = 6;
+const FAN_DABURU_RIICHI: u64 = 18;
+const FAN_IPPATSU: u64 = 30;
+const FAN_TENHOU: u64 = 35;
+const FAN_CHIIHOU: u64 = 36;
+
+// every win in the record
+pub fn parse_paipu(json: &str) -> Result<Vec<RecordedAgari>, ReplayError> {
+    let error = |context: &str, reason: String| ReplayError {
+        context: context.to_string(),
+        reason,
+    };
+    let root: Value = serde_json::from_str(json).map_err(|err| error("paipu", err.to_string()))?;
+
+    let mut records = Vec::new();
+    collect_records(&root, &mut records);
+    if records.is_empty() {
+        return Err(error("paipu", "no game records found".to_string()));
+    }
+
+    let mut agari_list = Vec::new();
+    let mut round: Option<Round> = None;
+    for (name, data) in records {
+        match name {
+            "RecordNewRound" => {
+                round = Some(Round::read(data).map_err(|reason| error(name, reason))?);
+            }
+            "RecordHule" => {
+                let round = round
+                    .as_ref()
+                    .ok_or_else(|| error(name, "win before the first round".to_string()))?;
+                let context = round_label(round.kyoku(), round.honba);
+                let hules = data["hules"]
+                    .as_array()
+                    .ok_or_else(|| error(&context, "missing hules".to_string()))?;
+                // score changes can only be told apart with a single winner
+                let deltas = (hules.len() == 1).then_some(&data["delta_scores"]);
+                for (i, hule) in hules.iter().enumerate() {
+                    let agari = read_hule(round, hule, deltas, i == 0)
+                        .map_err(|reason| error(&context, reason))?;
+                    agari_list.push(agari);
+                }
+            }
+            "RecordBaBei" => {
+                return Err(error(
+                    name,
+                    "three-player games are not supported".to_string(),
+                ));
+            }
+            _ => {
+                if let Some(round) = round.as_mut() {
+                    round.follow(name, data);
+                }
+            }
+        }
+    }
+    Ok(agari_list)
+}
+
+// (record name without ".lq.", data) in game order
+fn collect_records<'a>(value: &'a Value, records: &mut Vec<(&'a str, &'a Value)>) {
+    match value {
+        Value::Array(list) => {
+            for entry in list {
+                // newer records wrap each action as {"type": .., "result": {..}}
+                let entry = entry.get("result").unwrap_or(entry);
+                if let Some(name) = entry["name"].as_str() {
+                    records.push((name.trim_start_matches(".lq."), &entry["data"]));
+                }
+            }
+        }
+        Value::Object(map) => {
+            if let Some(inner) = ["records", "actions", "data"]
+                .iter()
+                .find_map(|key| map.get(*key))
+            {
+                collect_records(inner, records);
+            }
+        }
+        _ => {}
+    }
+}
+
+// state of the current round
+struct Round {
+    chang: u8, // 場 (0 = East round)
+    ju: u8,    // 局, also the dealer's seat
+    honba: u8,
+    kyoutaku: u8, // sticks on the table, including riichi declared this round
+    last_discarder: Option<usize>,
+}
+
+impl Round {
+    fn read(data: &Value) -> Result<Self, String> {
+        let number = |key: &str| {
+            data[key]
+                .as_u64()
+                .map(|n| n as u8)
+                .ok_or_else(|| format!("missing {}", key))
+        };
+        if data["scores"]
+            .as_array()
+            .is_some_and(|scores| scores.len() != 4)
+        {
+            return Err("three-player games are not supported".to_string());
+        }
+        Ok(Round {
+            chang: number("chang")?,
+            ju: number("ju")?,
+            honba: number("ben").unwrap_or(0),
+            kyoutaku: number("liqibang").unwrap_or(0),
+            last_discarder: None,
+        })
+    }
+
+    // kyoku counter as in the other importers (0 = East 1, 4 = South 1, ...)
+    fn kyoku(&self) -> u8 {
+        self.chang * 4 + self.ju
+    }
+
+    fn follow(&mut self, name: &str, data: &Value) {
+        // a riichi stick goes down with the action after the declaration
+        if data["liqi"].is_object() {
+            self.kyoutaku += 1;
+        }
+        // a discard or an added kan can be ronned
+        if name == "RecordDiscardTile" || name == "RecordAnGangAddGang" {
+            self.last_discarder = data["seat"].as_u64().map(|seat| seat as usize);
+        }
+    }
+}
+
+fn read_hule(
+    round: &Round,
+    hule: &Value,
+    deltas: Option<&Value>,
+    collects_sticks: bool,
+) -> Result<RecordedAgari, String> {
+    let seat = hule["seat"].as_u64().ok_or("missing seat")? as usize;
+    let dealer = round.ju as usize;
+    let is_tsumo = hule["zimo"].as_bool().unwrap_or(false);
+    let hand = strings(&hule["hand"])?;
+    let hu_tile = hule["hu_tile"].as_str().ok_or("missing hu_tile")?;
+    let melds = strings(&hule["ming"])?;
+
+    let mut builder = UserInput::builder()
+        .hand(tiles(&hand)?)
+        .win_on(tile(hu_tile)?)
+        .seat(seat_of(seat, dealer))
+        .round(seat_of(round.chang as usize, 0))
+        .dora(tiles(&strings(&hule["doras"])?)?)
+        .ura_dora(tiles(&strings(&hule["li_doras"])?)?);
+    if collects_sticks {
+        builder = builder.honba(round.honba).kyoutaku(round.kyoutaku);
+    }
+    builder = if is_tsumo {
+        builder.tsumo()
+    } else {
+        let discarder = round.last_discarder.ok_or("ron without a discard")?;
+        builder.ron(seat_of(discarder, dealer))
+    };
+
+    for meld in &melds {
+        let (meld_tiles, set) = parse_meld(meld)?;
+        let meld_tiles = tiles(&meld_tiles)?;
+        let reds = meld_tiles.iter().filter(|t| t.is_red()).count() as u8;
+        let lowest = meld_tiles
+            .iter()
+            .map(|t| t.plain())
+            .min_by_key(tile_to_index)
+            .ok_or_else(|| format!("empty meld '{}'", meld))?;
+        builder = set(builder, lowest).meld_akadora(reds);
+    }
+
+    let fans: Vec<(u64, u32)> = hule["fans"]
+        .as_array()
+        .ok_or("missing fans")?
+        .iter()
+        .map(|fan| {
+            (
+                fan["id"].as_u64().unwrap_or(0),
+                fan["val"].as_u64().unwrap_or(0) as u32,
+            )
+        })
+        .collect();
+    builder = apply_situational_yaku(builder, &fans);
+
+    let is_yakuman = hule["yiman"].as_bool().unwrap_or(false);
+    let fan_total: u32 = fans.iter().map(|(_, val)| val).sum();
+    let point = |key: &str| hule[key].as_u64().unwrap_or(0) as u32;
+    let points = match (is_tsumo, hule["qinjia"].as_bool().unwrap_or(false)) {
+        (true, true) => point("point_zimo_xian") * 3,
+        (true, false) => point("point_zimo_qin") + point("point_zimo_xian") * 2,
+        (false, _) => point("point_rong"),
+    };
+    let point_deltas = match deltas.and_then(Value::as_array) {
+        Some(list) if list.len() == 4 => {
+            let mut by_seat = [0i32; 4];
+            for (player, delta) in list.iter().enumerate() {
+                by_seat[seat_of(player, dealer) as usize] = delta.as_i64().unwrap_or(0) as i32;
+            }
+            Some(by_seat)
+        }
+        _ => None,
+    };
+
+    Ok(RecordedAgari {
+        round: round_label(round.kyoku(), round.honba),
+        winner: seat,
+        input: builder.build(),
+        expected: ExpectedScore {
+            han: if is_yakuman {
+                fan_total * 13
+            } else {
+                fan_total
+            } as u8,
+            fu: (!is_yakuman).then(|| hule["fu"].as_u64().unwrap_or(0) as u8),
+            points,
+            point_deltas,
+        },
+    })
+}
+
+type Setter = fn(UserInputBuilder) -> UserInputBuilder;
+type MeldSetter = fn(UserInputBuilder, Hai) -> UserInputBuilder;
+
+fn apply_situational_yaku(mut builder: UserInputBuilder, fans: &[(u64, u32)]) -> UserInputBuilder {
+    let setters: [(u64, Setter); 9] = [
+        (FAN_RIICHI, UserInputBuilder::riichi),
+        (FAN_DABURU_RIICHI, UserInputBuilder::double_riichi),
+        (FAN_IPPATSU, UserInputBuilder::ippatsu),
+        (FAN_CHANKAN, UserInputBuilder::chankan),
+        (FAN_RINSHAN, UserInputBuilder::rinshan),
+        (FAN_HAITEI, UserInputBuilder::haitei),
+        (FAN_HOUTEI, UserInputBuilder::houtei),
+        (FAN_TENHOU, UserInputBuilder::tenhou),
+        (FAN_CHIIHOU, UserInputBuilder::chiihou),
+    ];
+    for (id, set) in setters {
+        if fans.iter().any(|(fan, _)| *fan == id) {
+            builder = set(builder);
+        }
+    }
+    builder
+}
+
+// "shunzi(4s,0s,6s)", "kezi(..)", "minggang(..)", "angang(..)"
+fn parse_meld(text: &str) -> Result<(Vec<&str>, MeldSetter), String> {
+    let bad = || format!("cannot read meld '{}'", text);
+    let (kind, rest) = text.split_once('(').ok_or_else(bad)?;
+    let meld_tiles: Vec<&str> = rest.trim_end_matches(')').split(',').collect();
+    let set: MeldSetter = match kind {
+        "shunzi" => UserInputBuilder::chii,
+        "kezi" => UserInputBuilder::pon,
+        "minggang" => UserInputBuilder::open_kan,
+        "angang" => UserInputBuilder::closed_kan,
+        _ => return Err(bad()),
+    };
+    Ok((meld_tiles, set))
+}
+
+fn tile(text: &str) -> Result<Hai, String> {
+    text.parse()
+        .map_err(|_| format!("'{}' is not a tile", text))
+}
+
+fn tiles(texts: &[&str]) -> Result<Vec<Hai>, String> {
+    texts.iter().map(|text| tile(text)).collect()
+}
+
+fn strings(value: &Value) -> Result<Vec<&str>, String> {
+    let Some(list) = value.as_array() else {
+        return Ok(Vec::new());
+    };
+    list.iter()
+        .map(|entry| {
+            entry
+                .as_str()
+                .ok_or_else(|| format!("{} is not a string", entry))
+        })
+        .collect()
+}
@@ -0,0 +1,523 @@
+// mjai event stream (one JSON event per line)
+//
+//   {"type":"start_kyoku","bakaze":"E","kyoku":1,"honba":0,"kyotaku":0,"oya":0,
+//    "dora_marker":"5s","tehais":[[13 tiles] x4], ...}
+//   {"type":"tsumo","actor":0,"pai":"3m"}  {"type":"dahai","actor":0,"pai":"3m","tsumogiri":true}
+//   {"type":"chi"|"pon"|"daiminkan","actor":1,"target":0,"pai":"3m","consumed":["4m","5mr"]}
+//   {"type":"ankan","actor":1,"consumed":[4 tiles]}  {"type":"kakan","actor":1,"pai":"E","consumed":[3 tiles]}
+//   {"type":"reach","actor":2}  {"type":"reach_accepted","actor":2}  {"type":"dora","dora_marker":"7p"}
+//   {"type":"hora","actor":1,"target":0,"deltas":[...],"ura_markers":[...]}
+//
+// Tiles are 1m-9m, 1p-9p, 1s-9s, E S W N P F C, with "5mr" for a red five
+// and "?" for a tile the log's viewer cannot see. The tracker follows every
+// player's hand and the situational yaku (riichi, ippatsu, rinshan, chankan,
+// haitei, houtei, tenhou, chiihou), so a hora event needs no more than its
+// actor and target.
+
+use super::{Discrepancy, round_label, seat_of};
+use crate::implements::calculate_agari;
+use crate::implements::types::{
+    builder::UserInputBuilder,
+    error::{AgariError, ReplayError},
+    input::UserInput,
+    rules::RuleSet,
+    scoring::AgariResult,
+    tiles::{Hai, Kaze, tile_to_index},
+};
+use serde_json::Value;
+
+// draws from the live wall: 136 tiles, 52 dealt, 14 in the dead wall
+const LIVE_WALL: u8 = 70;
+
+#[derive(Debug, Clone)]
+// one hora event, scored by the engine
+pub struct MjaiHora {
+    pub round: String, // e.g. "East 2, 1 honba"
+    pub actor: usize,  // player index in the stream
+    pub input: Result<UserInput, AgariError>,
+    pub result: Result<AgariResult, AgariError>,
+    pub expected_deltas: Option<[i32; 4]>, // engine's score changes, by player index
+    pub event_deltas: Option<[i32; 4]>,    // the event's "deltas", by player index
+}
+
+impl MjaiHora {
+    // engine errors and disagreeing score changes
+    pub fn discrepancies(&self) -> Vec<Discrepancy> {
+        let mut discrepancies = Vec::new();
+        if let Err(err) = &self.result {
+            discrepancies.push(Discrepancy {
+                field: "result",
+                expected: "a scored hand".to_string(),
+                actual: err.to_string(),
+            });
+        }
+        if let (Some(expected), Some(recorded)) = (self.expected_deltas, self.event_deltas)
+            && expected != recorded
+        {
+            discrepancies.push(Discrepancy {
+                field: "deltas",
+                expected: format!("{:?}", recorded),
+                actual: format!("{:?}", expected),
+            });
+        }
+        discrepancies
+    }
+}
+
+// every hora in an event stream
+pub fn parse_events(text: &str, rules: &RuleSet) -> Result<Vec<MjaiHora>, ReplayError> {
+    let mut tracker = MjaiTracker::new(rules.clone());
+    let mut hora_list = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let hora = tracker.feed(line).map_err(|err| ReplayError {
+            context: format!("line {}", i + 1),
+            reason: err.to_string(),
+        })?;
+        hora_list.extend(hora);
+    }
+    Ok(hora_list)
+}
+
+#[derive(Debug, Clone, Default)]
+// game state built up one event at a time
+pub struct MjaiTracker {
+    rules: RuleSet,
+    kyoku: Option<Kyoku>,
+}
+
+impl MjaiTracker {
+    pub fn new(rules: RuleSet) -> Self {
+        MjaiTracker { rules, kyoku: None }
+    }
+
+    // apply one event; a hora event is scored and returned
+    pub fn feed(&mut self, line: &str) -> Result<Option<MjaiHora>, ReplayError> {
+        let event: Value = serde_json::from_str(line).map_err(|err| ReplayError {
+            context: "event".to_string(),
+            reason: err.to_string(),
+        })?;
+        let kind = event["type"].as_str().unwrap_or("");
+        self.apply(kind, &event).map_err(|reason| ReplayError {
+            context: kind.to_string(),
+            reason,
+        })
+    }
+
+    fn apply(&mut self, kind: &str, event: &Value) -> Result<Option<MjaiHora>, String> {
+        if kind == "start_kyoku" {
+            self.kyoku = Some(Kyoku::start(event)?);
+            return Ok(None);
+        }
+        let Some(kyoku) = self.kyoku.as_mut() else {
+            // start_game, end_game and anything before the first kyoku
+            return Ok(None);
+        };
+        let actor = || {
+            event["actor"]
+                .as_u64()
+                .map(|actor| actor as usize)
+                .filter(|actor| *actor < 4)
+                .ok_or("missing actor".to_string())
+        };
+
+        match kind {
+            "tsumo" => kyoku.tsumo(actor()?, &event["pai"])?,
+            "dahai" => kyoku.dahai(actor()?, &event["pai"])?,
+            "chi" | "pon" | "daiminkan" => kyoku.call(kind, actor()?, event)?,
+            "ankan" => kyoku.ankan(actor()?, event)?,
+            "kakan" => kyoku.kakan(actor()?, event)?,
+            "reach" => kyoku.reach(actor()?),
+            "reach_accepted" => kyoku.reach_accepted(actor()?),
+            "dora" => kyoku.dora_markers.push(pai(&event["dora_marker"])?),
+            "hora" => return kyoku.hora(actor()?, event, &self.rules).map(Some),
+            _ => {}
+        }
+        Ok(None)
+    }
+}
+
+// Round state
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CallKind {
+    Chii,
+    Pon,
+    OpenKan,
+    ClosedKan,
+    AddedKan,
+}
+
+#[derive(Debug, Clone)]
+struct Call {
+    kind: CallKind,
+    tiles: Vec<Hai>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Player {
+    hand: Vec<Hai>,
+    calls: Vec<Call>,
+    hidden: bool, // "?" tiles: the hand cannot be scored
+    discards: u32,
+    riichi: bool,
+    double_riichi: bool,
+    ippatsu: bool,
+    declaring: bool,    // reach announced, stick not yet down
+    rinshan_draw: bool, // last draw was from the dead wall
+    after_kan: bool,    // next draw comes from the dead wall
+}
+
+#[derive(Debug, Clone)]
+struct Kyoku {
+    bakaze: Kaze,
+    kyoku: u8, // 0-3 within the wind
+    honba: u8,
+    kyotaku: u8, // sticks on the table, including riichi accepted this round
+    oya: usize,
+    dora_markers: Vec<Hai>,
+    players: [Player; 4],
+    live_tiles: u8,
+    any_call: bool, // a call or kan has interrupted the first go-around
+    last_discard: Option<(usize, Hai)>,
+    last_kakan: Option<(usize, Hai)>,
+    hora_count: u8,
+}
+
+impl Kyoku {
+    fn start(event: &Value) -> Result<Self, String> {
+        let number = |key: &str| event[key].as_u64().unwrap_or(0) as u8;
+        let bakaze = match event["bakaze"].as_str() {
+            Some("E") => Kaze::Ton,
+            Some("S") => Kaze::Nan,
+            Some("W") => Kaze::Shaa,
+            Some("N") => Kaze::Pei,
+            _ => return Err("missing bakaze".to_string()),
+        };
+        let tehais = event["tehais"].as_array().ok_or("missing tehais")?;
+        if tehais.len() != 4 {
+            return Err("three-player games are not supported".to_string());
+        }
+
+        let mut players: [Player; 4] = Default::default();
+        for (player, tehai) in players.iter_mut().zip(tehais) {
+            for tile in tehai.as_array().ok_or("tehai is not a list")? {
+                match tile.as_str() {
+                    Some("?") => player.hidden = true,
+                    _ => player.hand.push(pai(tile)?),
+                }
+            }
+        }
+
+        Ok(Kyoku {
+            bakaze,
+            kyoku: number("kyoku").saturating_sub(1),
+            honba: number("honba"),
+            kyotaku: number("kyotaku"),
+            oya: number("oya") as usize % 4,
+            dora_markers: vec![pai(&event["dora_marker"])?],
+            players,
+            live_tiles: LIVE_WALL,
+            any_call: false,
+            last_discard: None,
+            last_kakan: None,
+            hora_count: 0,
+        })
+    }
+
+    fn tsumo(&mut self, actor: usize, tile: &Value) -> Result<(), String> {
+        // a kan's replacement draw still takes one tile off the live wall
+        self.live_tiles = self.live_tiles.saturating_sub(1);
+        let player = &mut self.players[actor];
+        player.rinshan_draw = player.after_kan;
+        player.after_kan = false;
+        if tile.as_str() != Some("?") {
+            player.hand.push(pai(tile)?);
+        }
+        Ok(())
+    }
+
+    fn dahai(&mut self, actor: usize, tile: &Value) -> Result<(), String> {
+        let tile = pai(tile)?;
+        let player = &mut self.players[actor];
+        if !player.hidden {
+            remove(&mut player.hand, tile)?;
+        }
+        // ippatsu ends with the riichi player's next discard
+        if player.riichi {
+            player.ippatsu = false;
+        }
+        player.discards += 1;
+        player.rinshan_draw = false;
+        self.last_discard = Some((actor, tile));
+        self.last_kakan = None;
+        Ok(())
+    }
+
+    fn call(&mut self, kind: &str, actor: usize, event: &Value) -> Result<(), String> {
+        let called = pai(&event["pai"])?;
+        let consumed = pais(&event["consumed"])?;
+        let kind = match kind {
+            "chi" => CallKind::Chii,
+            "pon" => CallKind::Pon,
+            _ => CallKind::OpenKan,
+        };
+        self.interrupt();
+        let player = &mut self.players[actor];
+        if !player.hidden {
+            for tile in &consumed {
+                remove(&mut player.hand, *tile)?;
+            }
+        }
+        player.after_kan = kind == CallKind::OpenKan;
+        let mut tiles = consumed;
+        tiles.push(called);
+        player.calls.push(Call { kind, tiles });
+        Ok(())
+    }
+
+    fn ankan(&mut self, actor: usize, event: &Value) -> Result<(), String> {
+        let consumed = pais(&event["consumed"])?;
+        self.interrupt();
+        let player = &mut self.players[actor];
+        if !player.hidden {
+            for tile in &consumed {
+                remove(&mut player.hand, *tile)?;
+            }
+        }
+        player.after_kan = true;
+        player.calls.push(Call {
+            kind: CallKind::ClosedKan,
+            tiles: consumed,
+        });
+        Ok(())
+    }
+
+    fn kakan(&mut self, actor: usize, event: &Value) -> Result<(), String> {
+        let added = pai(&event["pai"])?;
+        self.interrupt();
+        let player = &mut self.players[actor];
+        if !player.hidden {
+            remove(&mut player.hand, added)?;
+        }
+        let pon = player
+            .calls
+            .iter_mut()
+            .find(|call| call.kind == CallKind::Pon && call.tiles[0].plain() == added.plain())
+            .ok_or("kakan without its pon")?;
+        pon.kind = CallKind::AddedKan;
+        pon.tiles.push(added);
+        player.after_kan = true;
+        self.last_kakan = Some((actor, added));
+        Ok(())
+    }
+
+    fn reach(&mut self, actor: usize) {
+        let any_call = self.any_call;
+        let player = &mut self.players[actor];
+        player.declaring = true;
+        player.double_riichi = player.discards == 0 && !any_call;
+    }
+
+    fn reach_accepted(&mut self, actor: usize) {
+        let player = &mut self.players[actor];
+        if player.declaring {
+            player.declaring = false;
+            player.riichi = true;
+            player.ippatsu = true;
+            self.kyotaku += 1;
+        }
+    }
+
+    // any call breaks ippatsu and the first go-around
+    fn interrupt(&mut self) {
+        self.any_call = true;
+        for player in &mut self.players {
+            player.ippatsu = false;
+        }
+    }
+
+    fn hora(&mut self, actor: usize, event: &Value, rules: &RuleSet) -> Result<MjaiHora, String> {
+        let target = event["target"]
+            .as_u64()
+            .map_or(actor, |target| target as usize);
+        let is_tsumo = target == actor;
+        // honba and sticks go to the first winner of a double ron
+        let collects_sticks = self.hora_count == 0;
+        self.hora_count += 1;
+
+        let player = &self.players[actor];
+        if player.hidden {
+            return Err(format!("hand of player {} is hidden", actor));
+        }
+        let mut hand = player.hand.clone();
+        let is_chankan = !is_tsumo && self.last_kakan.is_some();
+        let winning = match (&event["pai"], is_tsumo) {
+            (tile @ Value::String(_), _) => pai(tile)?,
+            (_, true) => *hand.last().ok_or("empty hand")?,
+            (_, false) => self
+                .last_kakan
+                .or(self.last_discard)
+                .map(|(_, tile)| tile)
+                .ok_or("ron without a discard")?,
+        };
+        if is_tsumo {
+            remove(&mut hand, winning)?;
+        }
+
+        let ura_markers = if event["ura_markers"].is_array() {
+            &event["ura_markers"]
+        } else {
+            &event["uradora_markers"]
+        };
+        let mut builder = UserInput::builder()
+            .hand(hand.iter().copied())
+            .win_on(winning)
+            .seat(seat_of(actor, self.oya))
+            .round(self.bakaze)
+            .dora(self.dora_markers.iter().copied())
+            .ura_dora(pais(ura_markers)?);
+        if collects_sticks {
+            builder = builder.honba(self.honba).kyoutaku(self.kyotaku);
+        }
+        builder = if is_tsumo {
+            builder.tsumo()
+        } else {
+            builder.ron(seat_of(target, self.oya))
+        };
+
+        for call in &player.calls {
+            let lowest = call
+                .tiles
+                .iter()
+                .map(|tile| tile.plain())
+                .min_by_key(tile_to_index)
+                .ok_or("empty call")?;
+            builder = match call.kind {
+                CallKind::Chii => builder.chii(lowest),
+                CallKind::Pon => builder.pon(lowest),
+                CallKind::OpenKan => builder.open_kan(lowest),
+                CallKind::ClosedKan => builder.closed_kan(lowest),
+                CallKind::AddedKan => builder.added_kan(lowest),
+            };
+            let reds = call.tiles.iter().filter(|tile| tile.is_red()).count();
+            builder = builder.meld_akadora(reds as u8);
+        }
+
+        let first_draw = player.discards == 0 && !self.any_call;
+        let flags: [(bool, Setter); 9] = [
+            (
+                player.riichi && !player.double_riichi,
+                UserInputBuilder::riichi,
+            ),
+            (
+                player.riichi && player.double_riichi,
+                UserInputBuilder::double_riichi,
+            ),
+            (player.ippatsu, UserInputBuilder::ippatsu),
+            (is_tsumo && player.rinshan_draw, UserInputBuilder::rinshan),
+            (is_chankan, UserInputBuilder::chankan),
+            (
+                is_tsumo && self.live_tiles == 0 && !player.rinshan_draw,
+                UserInputBuilder::haitei,
+            ),
+            (
+                !is_tsumo && self.live_tiles == 0 && !is_chankan,
+                UserInputBuilder::houtei,
+            ),
+            (
+                is_tsumo && first_draw && actor == self.oya,
+                UserInputBuilder::tenhou,
+            ),
+            (
+                is_tsumo && first_draw && actor != self.oya,
+                UserInputBuilder::chiihou,
+            ),
+        ];
+        for (applies, set) in flags {
+            if applies {
+                builder = set(builder);
+            }
+        }
+
+        let input = builder.build();
+        let result = input
+            .as_ref()
+            .map_err(Clone::clone)
+            .and_then(|input| calculate_agari(input, rules));
+        let expected_deltas = result.as_ref().ok().and_then(|result| {
+            let mut by_player = [0i32; 4];
+            for (player, delta) in by_player.iter_mut().enumerate() {
+                *delta = result.point_delta(seat_of(player, self.oya))?;
+            }
+            Some(by_player)
+        });
+        let event_deltas = event["deltas"]
+            .as_array()
+            .filter(|list| list.len() == 4)
+            .map(|list| {
+                let mut by_player = [0i32; 4];
+                for (delta, value) in by_player.iter_mut().zip(list) {
+                    *delta = value.as_i64().unwrap_or(0) as i32;
+                }
+                by_player
+            });
+
+        Ok(MjaiHora {
+            round: round_label(self.bakaze as u8 * 4 + self.kyoku, self.honba),
+            actor,
+            input,
+            result,
+            expected_deltas,
+            event_deltas,
+        })
+    }
+}
+
+type Setter = fn(UserInputBuilder) -> UserInputBuilder;
+
+// Tiles
+
+// "3m", "5pr" (red five), "E" .. "N", "P" (haku), "F" (hatsu), "C" (chun)
+fn pai(value: &Value) -> Result<Hai, String> {
+    let text = value.as_str().ok_or("missing tile")?;
+    let not_a_tile = || format!("'{}' is not a tile", text);
+    let (mpsz, red) = match text {
+        "E" => ("1z", false),
+        "S" => ("2z", false),
+        "W" => ("3z", false),
+        "N" => ("4z", false),
+        "P" => ("5z", false),
+        "F" => ("6z", false),
+        "C" => ("7z", false),
+        // honors are letters and red fives are "5mr", never "1z" or "0m"
+        _ => match text.as_bytes() {
+            [b'1'..=b'9', b'm' | b'p' | b's'] => (text, false),
+            [b'5', b'm' | b'p' | b's', b'r'] => (&text[..2], true),
+            _ => return Err(not_a_tile()),
+        },
+    };
+    let hai: Hai = mpsz.parse().map_err(|_| not_a_tile())?;
+    Ok(if red { hai.red() } else { hai })
+}
+
+fn pais(value: &Value) -> Result<Vec<Hai>, String> {
+    let Some(list) = value.as_array() else {
+        return Ok(Vec::new());
+    };
+    list.iter().map(pai).collect()
+}
+
+// exact tile first, then the other copy of a five
+fn remove(hand: &mut Vec<Hai>, tile: Hai) -> Result<(), String> {
+    let position = hand
+        .iter()
+        .position(|t| *t == tile)
+        .or_else(|| hand.iter().position(|t| t.plain() == tile.plain()))
+        .ok_or_else(|| format!("{} is not in hand", tile))?;
+    hand.remove(position);
+    Ok(())
+}
@@ -0,0 +1,132 @@
+// Game record import and score verification
+//
+// Importers turn a log into RecordedAgari values: the UserInput rebuilt from
+// the log plus the score the log recorded. verify() scores the input with
+// the engine and lists every field that disagrees.
+
+pub mod majsoul;
+pub mod mjai;
+pub mod tenhou;
+pub mod tenhou6;
+
+use crate::implements::calculate_agari;
+use crate::implements::score_calculator::yakuman::count_yakuman;
+use crate::implements::types::{
+    error::AgariError,
+    game::AgariType,
+    input::UserInput,
+    rules::RuleSet,
+    scoring::{AgariResult, HandLimit},
+    tiles::Kaze,
+};
+
+const WINDS: [Kaze; 4] = [Kaze::Ton, Kaze::Nan, Kaze::Shaa, Kaze::Pei];
+const WIND_NAMES: [&str; 4] = ["East", "South", "West", "North"];
+
+#[derive(Debug, Clone)]
+// one win from a game record
+pub struct RecordedAgari {
+    pub round: String, // e.g. "East 2, 1 honba"
+    pub winner: usize, // player index in the log
+    pub input: Result<UserInput, AgariError>,
+    pub expected: ExpectedScore,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+// score as recorded by the log
+pub struct ExpectedScore {
+    pub han: u8,
+    pub fu: Option<u8>,                 // not compared for yakuman
+    pub points: u32,                    // hand value without honba and sticks
+    pub point_deltas: Option<[i32; 4]>, // by seat wind, honba and sticks included
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+// field where the engine and the log disagree
+pub struct Discrepancy {
+    pub field: &'static str,
+    pub expected: String,
+    pub actual: String,
+}
+
+// score the recorded input and compare it with the log
+pub fn verify(agari: &RecordedAgari, rules: &RuleSet) -> Vec<Discrepancy> {
+    let result = match agari.input.as_ref() {
+        Ok(input) => calculate_agari(input, rules),
+        Err(err) => Err(err.clone()),
+    };
+    let result = match result {
+        Ok(result) => result,
+        Err(err) => {
+            return vec![Discrepancy {
+                field: "result",
+                expected: format!("{} han", agari.expected.han),
+                actual: err.to_string(),
+            }];
+        }
+    };
+
+    let expected = &agari.expected;
+    let mut discrepancies = Vec::new();
+    let mut compare = |field: &'static str, expected: String, actual: String| {
+        if expected != actual {
+            discrepancies.push(Discrepancy {
+                field,
+                expected,
+                actual,
+            });
+        }
+    };
+
+    // logs record 13 per yakuman; the engine adds the regular yaku on top
+    let han = match count_yakuman(&result.yaku_list, rules) {
+        0 => result.han as u32,
+        count => 13 * count,
+    };
+    compare("han", expected.han.to_string(), han.to_string());
+    if let Some(fu) = expected.fu
+        && result.limit_name != Some(HandLimit::Yakuman)
+    {
+        compare("fu", fu.to_string(), result.fu.to_string());
+    }
+    compare(
+        "points",
+        expected.points.to_string(),
+        base_points(&result, rules).to_string(),
+    );
+    if let Some(deltas) = expected.point_deltas {
+        compare(
+            "point_deltas",
+            format!("{:?}", deltas),
+            result
+                .point_deltas
+                .map_or("unknown".to_string(), |actual| format!("{:?}", actual)),
+        );
+    }
+    discrepancies
+}
+
+// total payment without the honba bonus (三人麻雀 has two tsumo payers)
+fn base_points(result: &AgariResult, rules: &RuleSet) -> u32 {
+    let honba = result.honba as u32;
+    let collected = match result.agari_type {
+        AgariType::Ron => honba * rules.honba_value,
+        AgariType::Tsumo => honba * rules.honba_tsumo_value * if rules.sanma { 2 } else { 3 },
+    };
+    result.total_payment.saturating_sub(collected)
+}
+
+// seat wind of a player, counting from the dealer
+pub(crate) fn seat_of(player: usize, oya: usize) -> Kaze {
+    WINDS[(player + 4 - oya % 4) % 4]
+}
+
+// "East 1, 0 honba" from the round counter (0 = East 1, 4 = South 1, ...)
+pub(crate) fn round_label(kyoku: u8, honba: u8) -> String {
+    format!(
+        "{} {}, {} honba",
+        WIND_NAMES[(kyoku as usize / 4) % 4],
+        kyoku % 4 + 1,
+        honba
+    )
+}
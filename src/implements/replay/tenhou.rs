@@ -0,0 +1,369 @@
+// Tenhou mjlog (XML) import
+//
+//   <GO type="169"/>
+//   <INIT seed="kyoku,honba,kyoutaku,dice,dice,dora" oya="0" .../>
+//   <AGARI ba="honba,kyoutaku" hai="..." m="..." machi="id" ten="fu,points,limit"
+//          yaku="id,han,..." yakuman="id,..." doraHai="..." doraHaiUra="..."
+//          who="w" fromWho="f" sc="score,delta,..."/>
+//
+// - tiles are ids 0-135, four per kind (kind = id / 4, in engine index
+//   order); with red fives on, 16, 52 and 88 are the red 5m, 5p and 5s
+// - hai holds the concealed tiles including the winning tile (machi)
+// - situational yaku (riichi, ippatsu, haitei, ...) are taken from the
+//   recorded yaku list
+
+use super::{ExpectedScore, RecordedAgari, round_label, seat_of};
+use crate::implements::types::{
+    builder::UserInputBuilder,
+    error::ReplayError,
+    input::UserInput,
+    tiles::{Hai, index_to_tile},
+};
+use quick_xml::{Reader, events::BytesStart, events::Event};
+use std::collections::HashMap;
+
+// GO type flags
+const GAME_NO_AKA: i64 = 0x02;
+const GAME_SANMA: i64 = 0x10;
+
+const RED_FIVES: [u32; 3] = [16, 52, 88];
+
+// Tenhou yaku ids
+const YAKU_RIICHI: i64 = 1;
+const YAKU_IPPATSU: i64 = 2;
+const YAKU_CHANKAN: i64 = 3;
+const YAKU_RINSHAN: i64 = 4;
+const YAKU_HAITEI: i64 = 5;
+const YAKU_HOUTEI: i64 = 6;
+const YAKU_DABURU_RIICHI: i64 = 21;
+const YAKU_RENHOU: i64 = 36;
+const YAKU_TENHOU: i64 = 37;
+const YAKU_CHIIHOU: i64 = 38;
+
+// every AGARI in the log, in order
+pub fn parse_mjlog(xml: &str) -> Result<Vec<RecordedAgari>, ReplayError> {
+    let mut reader = Reader::from_str(xml);
+    let mut has_aka = true;
+    let mut round = Round::default();
+    let mut agari_list = Vec::new();
+
+    loop {
+        let event = reader.read_event().map_err(|err| ReplayError {
+            context: format!("byte {}", reader.buffer_position()),
+            reason: err.to_string(),
+        })?;
+        let tag = match event {
+            Event::Empty(tag) | Event::Start(tag) => tag,
+            Event::Eof => break,
+            _ => continue,
+        };
+
+        match tag.name().as_ref() {
+            b"GO" => {
+                let attrs = attributes(&tag, "GO")?;
+                let game_type = number(&attrs, "type", "GO")?;
+                if game_type & GAME_SANMA != 0 {
+                    return Err(ReplayError {
+                        context: "GO".to_string(),
+                        reason: "three-player games are not supported".to_string(),
+                    });
+                }
+                has_aka = game_type & GAME_NO_AKA == 0;
+            }
+            b"INIT" => {
+                let attrs = attributes(&tag, "INIT")?;
+                let seed = numbers(&attrs, "seed", "INIT")?;
+                round = Round {
+                    kyoku: *seed.first().unwrap_or(&0) as u8,
+                    oya: number(&attrs, "oya", "INIT")? as usize,
+                };
+            }
+            b"AGARI" => {
+                let context = format!("AGARI #{}", agari_list.len() + 1);
+                let attrs = attributes(&tag, &context)?;
+                let agari = read_agari(&attrs, &round, has_aka).map_err(|reason| ReplayError {
+                    context: context.clone(),
+                    reason,
+                })?;
+                agari_list.push(agari);
+            }
+            _ => {}
+        }
+    }
+    Ok(agari_list)
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Round {
+    kyoku: u8, // 0 = East 1, 4 = South 1, ...
+    oya: usize,
+}
+
+fn read_agari(
+    attrs: &HashMap<String, String>,
+    round: &Round,
+    has_aka: bool,
+) -> Result<RecordedAgari, String> {
+    let get = |key: &str| attrs.get(key).map(String::as_str).unwrap_or("");
+    let list = |key: &str| parse_numbers(get(key));
+
+    let who = get("who").parse::<usize>().map_err(|_| "missing who")?;
+    let from_who = get("fromWho")
+        .parse::<usize>()
+        .map_err(|_| "missing fromWho")?;
+    let ba = list("ba")?;
+    let (honba, kyoutaku) = (
+        *ba.first().unwrap_or(&0) as u8,
+        *ba.get(1).unwrap_or(&0) as u8,
+    );
+
+    let mut concealed: Vec<u32> = list("hai")?.into_iter().map(|id| id as u32).collect();
+    let machi = get("machi").parse::<u32>().map_err(|_| "missing machi")?;
+    let pos = concealed
+        .iter()
+        .position(|id| *id == machi)
+        .ok_or("winning tile is not in hai")?;
+    concealed.remove(pos);
+
+    let melds = list("m")?
+        .into_iter()
+        .map(|code| decode_meld(code as u32))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // seats relative to the dealer
+    let seat = seat_of(who, round.oya);
+    let mut builder = UserInput::builder()
+        .hand(tiles(&concealed, has_aka)?)
+        .win_on(tile(machi, has_aka)?)
+        .seat(seat)
+        .round(seat_of(round.kyoku as usize / 4, 0))
+        .honba(honba)
+        .kyoutaku(kyoutaku)
+        .dora(tiles(&to_ids(&list("doraHai")?), has_aka)?)
+        .ura_dora(tiles(&to_ids(&list("doraHaiUra")?), has_aka)?);
+    builder = if who == from_who {
+        builder.tsumo()
+    } else {
+        builder.ron(seat_of(from_who, round.oya))
+    };
+
+    for meld in &melds {
+        builder = meld.apply(builder, has_aka)?;
+    }
+
+    // yaku="id,han,id,han,..."; yakuman="id,..."
+    let yaku = list("yaku")?;
+    let yakuman = list("yakuman")?;
+    let yaku_ids: Vec<i64> = yaku
+        .chunks(2)
+        .map(|pair| pair[0])
+        .chain(yakuman.iter().copied())
+        .collect();
+    builder = apply_situational_yaku(builder, &yaku_ids);
+
+    let han = if yakuman.is_empty() {
+        yaku.chunks(2)
+            .map(|pair| pair.get(1).copied().unwrap_or(0))
+            .sum::<i64>()
+    } else {
+        13 * yakuman.len() as i64
+    };
+    let ten = list("ten")?;
+    let sc = list("sc")?;
+    let point_deltas = (sc.len() == 8).then(|| {
+        let mut deltas = [0i32; 4];
+        for player in 0..4 {
+            deltas[seat_of(player, round.oya) as usize] = sc[player * 2 + 1] as i32 * 100;
+        }
+        deltas
+    });
+
+    Ok(RecordedAgari {
+        round: round_label(round.kyoku, honba),
+        winner: who,
+        input: builder.build(),
+        expected: ExpectedScore {
+            han: han as u8,
+            fu: yakuman.is_empty().then(|| *ten.first().unwrap_or(&0) as u8),
+            points: *ten.get(1).ok_or("missing ten")? as u32,
+            point_deltas,
+        },
+    })
+}
+
+type Setter = fn(UserInputBuilder) -> UserInputBuilder;
+
+fn apply_situational_yaku(mut builder: UserInputBuilder, yaku_ids: &[i64]) -> UserInputBuilder {
+    let setters: [(i64, Setter); 10] = [
+        (YAKU_RIICHI, UserInputBuilder::riichi),
+        (YAKU_DABURU_RIICHI, UserInputBuilder::double_riichi),
+        (YAKU_IPPATSU, UserInputBuilder::ippatsu),
+        (YAKU_CHANKAN, UserInputBuilder::chankan),
+        (YAKU_RINSHAN, UserInputBuilder::rinshan),
+        (YAKU_HAITEI, UserInputBuilder::haitei),
+        (YAKU_HOUTEI, UserInputBuilder::houtei),
+        (YAKU_RENHOU, UserInputBuilder::renhou),
+        (YAKU_TENHOU, UserInputBuilder::tenhou),
+        (YAKU_CHIIHOU, UserInputBuilder::chiihou),
+    ];
+    for (id, set) in setters {
+        if yaku_ids.contains(&id) {
+            builder = set(builder);
+        }
+    }
+    builder
+}
+
+// Melds
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MeldKind {
+    Chii,
+    Pon,
+    AddedKan,
+    OpenKan,
+    ClosedKan,
+}
+
+#[derive(Debug, Clone)]
+struct Meld {
+    kind: MeldKind,
+    tiles: Vec<u32>, // tile ids
+}
+
+impl Meld {
+    fn apply(&self, builder: UserInputBuilder, has_aka: bool) -> Result<UserInputBuilder, String> {
+        let meld_tiles = tiles(&self.tiles, has_aka)?;
+        let reds = meld_tiles.iter().filter(|tile| tile.is_red()).count() as u8;
+        let lowest = *self.tiles.iter().min().ok_or("empty meld")?;
+        let tile = tile(lowest, false)?;
+        let builder = match self.kind {
+            MeldKind::Chii => builder.chii(tile),
+            MeldKind::Pon => builder.pon(tile),
+            MeldKind::AddedKan => builder.added_kan(tile),
+            MeldKind::OpenKan => builder.open_kan(tile),
+            MeldKind::ClosedKan => builder.closed_kan(tile),
+        };
+        Ok(builder.meld_akadora(reds))
+    }
+}
+
+// meld bit layout:
+//   chii     bit 2 set; bits 10-15 base/called, bits 3-8 copy of each tile
+//   pon      bit 3 set; bits 9-15 kind/called, bits 5-6 the copy left out
+//   shouminkan bit 4 set; same layout as pon
+//   kan      bits 2-5 clear; bits 8-15 tile id, bits 0-1 caller (0 = ankan)
+fn decode_meld(code: u32) -> Result<Meld, String> {
+    if code & 0x4 != 0 {
+        let pattern = (code & 0xFC00) >> 10;
+        let base = pattern / 3;
+        let kind = (base / 7) * 9 + base % 7;
+        let copies = [(code >> 3) & 3, (code >> 5) & 3, (code >> 7) & 3];
+        let tiles = (0..3)
+            .map(|i| (kind + i) * 4 + copies[i as usize])
+            .collect();
+        return Ok(Meld {
+            kind: MeldKind::Chii,
+            tiles,
+        });
+    }
+    if code & 0x18 != 0 {
+        let kind = ((code & 0xFE00) >> 9) / 3;
+        let all: Vec<u32> = (0..4).map(|copy| kind * 4 + copy).collect();
+        if code & 0x8 != 0 {
+            let unused = (code >> 5) & 3;
+            return Ok(Meld {
+                kind: MeldKind::Pon,
+                tiles: all.into_iter().filter(|id| id % 4 != unused).collect(),
+            });
+        }
+        return Ok(Meld {
+            kind: MeldKind::AddedKan,
+            tiles: all,
+        });
+    }
+    if code & 0x20 != 0 {
+        return Err("nukidora is not supported".to_string());
+    }
+    let kind = ((code & 0xFF00) >> 8) / 4;
+    Ok(Meld {
+        kind: if code & 3 == 0 {
+            MeldKind::ClosedKan
+        } else {
+            MeldKind::OpenKan
+        },
+        tiles: (0..4).map(|copy| kind * 4 + copy).collect(),
+    })
+}
+
+// Tiles
+
+fn tile(id: u32, has_aka: bool) -> Result<Hai, String> {
+    if id >= 136 {
+        return Err(format!("tile id {} is out of range", id));
+    }
+    let tile = index_to_tile(id as usize / 4);
+    Ok(if has_aka && RED_FIVES.contains(&id) {
+        tile.red()
+    } else {
+        tile
+    })
+}
+
+fn tiles(ids: &[u32], has_aka: bool) -> Result<Vec<Hai>, String> {
+    ids.iter().map(|id| tile(*id, has_aka)).collect()
+}
+
+fn to_ids(values: &[i64]) -> Vec<u32> {
+    values.iter().map(|value| *value as u32).collect()
+}
+
+// Attributes
+
+fn attributes(tag: &BytesStart, context: &str) -> Result<HashMap<String, String>, ReplayError> {
+    let error = |reason: String| ReplayError {
+        context: context.to_string(),
+        reason,
+    };
+    let mut attrs = HashMap::new();
+    for attr in tag.attributes() {
+        let attr = attr.map_err(|err| error(err.to_string()))?;
+        let key = String::from_utf8_lossy(attr.key.as_ref()).into_owned();
+        let value = attr
+            .unescape_value()
+            .map_err(|err| error(err.to_string()))?;
+        attrs.insert(key, value.into_owned());
+    }
+    Ok(attrs)
+}
+
+fn number(attrs: &HashMap<String, String>, key: &str, context: &str) -> Result<i64, ReplayError> {
+    let value = attrs.get(key).map(String::as_str).unwrap_or("");
+    value.parse().map_err(|_| ReplayError {
+        context: context.to_string(),
+        reason: format!("{}=\"{}\" is not a number", key, value),
+    })
+}
+
+fn numbers(
+    attrs: &HashMap<String, String>,
+    key: &str,
+    context: &str,
+) -> Result<Vec<i64>, ReplayError> {
+    parse_numbers(attrs.get(key).map(String::as_str).unwrap_or("")).map_err(|reason| ReplayError {
+        context: context.to_string(),
+        reason,
+    })
+}
+
+// "1,2,3"; empty text is an empty list
+fn parse_numbers(text: &str) -> Result<Vec<i64>, String> {
+    text.split(',')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            part.trim()
+                .parse()
+                .map_err(|_| format!("'{}' is not a number", part))
+        })
+        .collect()
+}
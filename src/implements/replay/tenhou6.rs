@@ -0,0 +1,798 @@
+// tenhou.net/6 viewer JSON import and export
+//
+//   {"name": [...], "rule": {"disp": "般南喰赤", "aka": 1}, "log": [round, ...]}
+//   round = [[kyoku, honba, kyoutaku], [scores x4], [dora], [ura dora],
+//            hai0, take0, discard0, ..., hai3, take3, discard3,
+//            ["和了", [deltas x4], [who, from, pao, "30符1飜1000点", "立直(1飜)", ...], ...]]
+//
+// - tiles are 11-19 man, 21-29 pin, 31-39 sou, 41-47 honors, 51-53 red fives
+// - calls sit in the take list ("c275226" chii, "p343434" pon, "m39393939"
+//   daiminkan, the letter before the called tile); ankan ("a") and shouminkan
+//   ("k") sit in the discard list; discard 60 is tsumogiri, "r" marks riichi
+// - player 0 is East in East 1; the dealer of round k is player k % 4
+// - situational yaku (riichi, ippatsu, haitei, ...) are taken from the
+//   recorded yaku list
+
+use super::{ExpectedScore, RecordedAgari, round_label, seat_of};
+use crate::implements::score_calculator::{han::get_han_value, yakuman::count_yakuman};
+use crate::implements::types::{
+    builder::UserInputBuilder,
+    error::ReplayError,
+    game::AgariType,
+    hand::MentsuType,
+    input::UserInput,
+    rules::RuleSet,
+    scoring::{AgariResult, HandLimit},
+    tiles::{Hai, Kaze, Sangenpai, Suhai, index_to_tile, tile_to_index},
+    yaku::Yaku,
+};
+use serde_json::{Value, json};
+
+const TSUMOGIRI: u32 = 60;
+const AGARI: &str = "和了";
+
+#[derive(Debug, Clone)]
+// one round (kyoku) of the log
+pub struct Tenhou6Round {
+    pub kyoku: u8, // 0 = East 1, 4 = South 1, ...
+    pub honba: u8,
+    pub kyoutaku: u8,
+    pub scores: [i32; 4], // by player, at the start of the round
+    pub outcome: String,  // 和了, 流局, ...
+    pub agari: Vec<RecordedAgari>,
+}
+
+// every round in the log, with its wins rebuilt for the engine
+pub fn parse_log(json: &str) -> Result<Vec<Tenhou6Round>, ReplayError> {
+    let error = |context: &str, reason: String| ReplayError {
+        context: context.to_string(),
+        reason,
+    };
+    let root: Value = serde_json::from_str(json).map_err(|err| error("log", err.to_string()))?;
+
+    let disp = root["rule"]["disp"].as_str().unwrap_or("");
+    if disp.contains('三') {
+        return Err(error(
+            "rule",
+            "three-player games are not supported".to_string(),
+        ));
+    }
+    let rounds = root["log"]
+        .as_array()
+        .ok_or_else(|| error("log", "missing log array".to_string()))?;
+
+    rounds
+        .iter()
+        .enumerate()
+        .map(|(i, round)| {
+            read_round(round).map_err(|reason| error(&format!("round #{}", i + 1), reason))
+        })
+        .collect()
+}
+
+fn read_round(round: &Value) -> Result<Tenhou6Round, String> {
+    let info = numbers(&round[0])?;
+    let (kyoku, honba, kyoutaku) = (
+        *info.first().ok_or("missing round info")? as u8,
+        *info.get(1).unwrap_or(&0) as u8,
+        *info.get(2).unwrap_or(&0) as u8,
+    );
+    let start = numbers(&round[1])?;
+    let mut scores = [0i32; 4];
+    for (score, value) in scores.iter_mut().zip(start) {
+        *score = value as i32;
+    }
+
+    let players: Vec<Player> = (0..4)
+        .map(|p| Player {
+            hai: &round[4 + p * 3],
+            takes: &round[5 + p * 3],
+            discards: &round[6 + p * 3],
+        })
+        .collect();
+    let result = round[16].as_array().ok_or("missing result")?;
+    let outcome = result
+        .first()
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .to_string();
+
+    // discarder of a ron, from the first winner's [who, from, ...]
+    let dealt_in = result
+        .get(2)
+        .and_then(|info| Some((info[0].as_u64()?, info[1].as_u64()?)))
+        .and_then(|(who, from)| (who != from).then_some(from as usize));
+    let context = Context {
+        kyoku,
+        honba,
+        kyoutaku: kyoutaku + riichi_sticks(&players, dealt_in) as u8,
+        dora: tiles(&codes(&round[2])?)?,
+        ura_dora: tiles(&codes(&round[3])?)?,
+    };
+
+    let mut agari = Vec::new();
+    if outcome == AGARI {
+        // deltas, info pairs; two pairs on a double ron, where only the
+        // first winner collects honba and sticks
+        for (i, pair) in result[1..].chunks(2).enumerate() {
+            let [deltas, info] = pair else {
+                return Err("unpaired result entry".to_string());
+            };
+            agari.push(read_agari(&context, &players, deltas, info, i == 0)?);
+        }
+    }
+
+    Ok(Tenhou6Round {
+        kyoku,
+        honba,
+        kyoutaku,
+        scores,
+        outcome,
+        agari,
+    })
+}
+
+struct Context {
+    kyoku: u8,
+    honba: u8,
+    kyoutaku: u8,
+    dora: Vec<Hai>,
+    ura_dora: Vec<Hai>,
+}
+
+struct Player<'a> {
+    hai: &'a Value,
+    takes: &'a Value,
+    discards: &'a Value,
+}
+
+fn read_agari(
+    context: &Context,
+    players: &[Player],
+    deltas: &Value,
+    info: &Value,
+    collects_sticks: bool,
+) -> Result<RecordedAgari, String> {
+    let info = info.as_array().ok_or("result info is not a list")?;
+    let who = info
+        .first()
+        .and_then(Value::as_u64)
+        .ok_or("missing winner")? as usize;
+    let from = info
+        .get(1)
+        .and_then(Value::as_u64)
+        .ok_or("missing discarder")? as usize;
+    let description = info.get(3).and_then(Value::as_str).unwrap_or("");
+    let yaku: Vec<&str> = info[4.min(info.len())..]
+        .iter()
+        .filter_map(Value::as_str)
+        .collect();
+    let winner = players.get(who).ok_or("winner out of range")?;
+    let dealer = context.kyoku as usize % 4;
+
+    let (mut hand, melds) = replay_hand(winner)?;
+    let is_tsumo = who == from;
+    let winning_code = if is_tsumo {
+        *hand.last().ok_or("empty hand")?
+    } else {
+        last_discard(players.get(from).ok_or("discarder out of range")?)?
+    };
+    if is_tsumo {
+        hand.pop();
+    }
+
+    let mut builder = UserInput::builder()
+        .hand(tiles(&hand)?)
+        .win_on(tile(winning_code)?)
+        .seat(seat_of(who, dealer))
+        .round(seat_of(context.kyoku as usize / 4, 0))
+        .dora(context.dora.iter().copied())
+        .ura_dora(context.ura_dora.iter().copied());
+    if collects_sticks {
+        builder = builder.honba(context.honba).kyoutaku(context.kyoutaku);
+    }
+    builder = if is_tsumo {
+        builder.tsumo()
+    } else {
+        builder.ron(seat_of(from, dealer))
+    };
+
+    for meld in &melds {
+        builder = meld.apply(builder)?;
+    }
+    builder = apply_situational_yaku(builder, &yaku);
+
+    let delta_list = numbers(deltas)?;
+    let point_deltas = (delta_list.len() == 4).then(|| {
+        let mut by_seat = [0i32; 4];
+        for (player, delta) in delta_list.iter().enumerate() {
+            by_seat[seat_of(player, dealer) as usize] = *delta as i32;
+        }
+        by_seat
+    });
+
+    Ok(RecordedAgari {
+        round: round_label(context.kyoku, context.honba),
+        winner: who,
+        input: builder.build(),
+        expected: ExpectedScore {
+            han: yaku.iter().map(|entry| yaku_han(entry)).sum::<u32>() as u8,
+            fu: fu_of(description),
+            points: points_of(description)?,
+            point_deltas,
+        },
+    })
+}
+
+// Hand replay
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CallKind {
+    Chii,
+    Pon,
+    OpenKan,
+    ClosedKan,
+    AddedKan,
+}
+
+#[derive(Debug, Clone)]
+struct Call {
+    kind: CallKind,
+    codes: Vec<u32>, // every tile of the meld
+}
+
+impl Call {
+    // "c275226": letter, then the called tile, then the player's own tiles
+    fn parse(text: &str) -> Result<(Self, usize), String> {
+        let letter_at = text
+            .find(|c: char| c.is_ascii_alphabetic())
+            .ok_or_else(|| format!("'{}' is not a call", text))?;
+        let kind = match &text[letter_at..=letter_at] {
+            "c" => CallKind::Chii,
+            "p" => CallKind::Pon,
+            "m" => CallKind::OpenKan,
+            "a" => CallKind::ClosedKan,
+            "k" => CallKind::AddedKan,
+            other => return Err(format!("unknown call '{}'", other)),
+        };
+        let digits: String = text.chars().filter(char::is_ascii_digit).collect();
+        let codes = digits
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| {
+                std::str::from_utf8(pair)
+                    .ok()
+                    .and_then(|code| code.parse().ok())
+                    .ok_or_else(|| format!("'{}' has a bad tile code", text))
+            })
+            .collect::<Result<Vec<u32>, _>>()?;
+        Ok((Call { kind, codes }, letter_at / 2))
+    }
+
+    fn apply(&self, builder: UserInputBuilder) -> Result<UserInputBuilder, String> {
+        let call_tiles = tiles(&self.codes)?;
+        let reds = call_tiles.iter().filter(|tile| tile.is_red()).count() as u8;
+        let lowest = call_tiles
+            .iter()
+            .map(|tile| tile.plain())
+            .min_by_key(tile_to_index)
+            .ok_or("empty call")?;
+        let builder = match self.kind {
+            CallKind::Chii => builder.chii(lowest),
+            CallKind::Pon => builder.pon(lowest),
+            CallKind::OpenKan => builder.open_kan(lowest),
+            CallKind::ClosedKan => builder.closed_kan(lowest),
+            CallKind::AddedKan => builder.added_kan(lowest),
+        };
+        Ok(builder.meld_akadora(reds))
+    }
+}
+
+// concealed tile codes and calls after the player's last action
+fn replay_hand(player: &Player) -> Result<(Vec<u32>, Vec<Call>), String> {
+    let mut hand = codes(player.hai)?;
+    let mut calls: Vec<Call> = Vec::new();
+    let takes = player.takes.as_array().map(Vec::as_slice).unwrap_or(&[]);
+    let discards = player.discards.as_array().map(Vec::as_slice).unwrap_or(&[]);
+    let mut last_draw = None;
+
+    for i in 0..takes.len().max(discards.len()) {
+        match takes.get(i) {
+            Some(Value::Number(code)) => {
+                let code = code.as_u64().ok_or("bad draw")? as u32;
+                hand.push(code);
+                last_draw = Some(code);
+            }
+            Some(Value::String(text)) => {
+                let (call, called_at) = Call::parse(text)?;
+                for (j, code) in call.codes.iter().enumerate() {
+                    if j != called_at {
+                        remove_code(&mut hand, *code)?;
+                    }
+                }
+                calls.push(call);
+                last_draw = None;
+            }
+            _ => {}
+        }
+
+        match discards.get(i) {
+            Some(Value::Number(code)) => match code.as_u64().ok_or("bad discard")? as u32 {
+                0 => {}
+                TSUMOGIRI => remove_code(&mut hand, last_draw.ok_or("tsumogiri without a draw")?)?,
+                code => remove_code(&mut hand, code)?,
+            },
+            Some(Value::String(text)) if text.starts_with('r') => {
+                let code: u32 = text[1..]
+                    .parse()
+                    .map_err(|_| format!("bad riichi '{}'", text))?;
+                let code = if code == TSUMOGIRI {
+                    last_draw.ok_or("tsumogiri without a draw")?
+                } else {
+                    code
+                };
+                remove_code(&mut hand, code)?;
+            }
+            Some(Value::String(text)) => {
+                let (call, added_at) = Call::parse(text)?;
+                match call.kind {
+                    CallKind::ClosedKan => {
+                        for code in &call.codes {
+                            remove_code(&mut hand, *code)?;
+                        }
+                        calls.push(call);
+                    }
+                    CallKind::AddedKan => {
+                        let added = call.codes[added_at];
+                        remove_code(&mut hand, added)?;
+                        // a red five can be added to a plain pon and the other way round
+                        let kind = tile(added)?.plain();
+                        let pon = calls
+                            .iter_mut()
+                            .find(|c| {
+                                c.kind == CallKind::Pon
+                                    && c.codes
+                                        .first()
+                                        .and_then(|code| tile(*code).ok())
+                                        .map(Hai::plain)
+                                        == Some(kind)
+                            })
+                            .ok_or("shouminkan without its pon")?;
+                        *pon = call;
+                    }
+                    _ => return Err(format!("'{}' is not a discard", text)),
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok((hand, calls))
+}
+
+// riichi declared this round; a declaration that deals in never puts its
+// stick down
+fn riichi_sticks(players: &[Player], dealt_in: Option<usize>) -> usize {
+    let declared = |discard: &Value| discard.as_str().is_some_and(|text| text.starts_with('r'));
+    players
+        .iter()
+        .enumerate()
+        .filter_map(|(i, player)| Some((i, player.discards.as_array()?)))
+        .map(|(i, discards)| {
+            let count = discards.iter().filter(|d| declared(d)).count();
+            match discards.last() {
+                Some(last) if dealt_in == Some(i) && declared(last) => count - 1,
+                _ => count,
+            }
+        })
+        .sum()
+}
+
+// the tile a discarder dealt in with (or added to a kan, for chankan)
+fn last_discard(player: &Player) -> Result<u32, String> {
+    let discards = player
+        .discards
+        .as_array()
+        .ok_or("discarder has no discards")?;
+    let takes = player.takes.as_array().map(Vec::as_slice).unwrap_or(&[]);
+    let last_draw = || {
+        takes
+            .iter()
+            .rev()
+            .find_map(Value::as_u64)
+            .map(|code| code as u32)
+            .ok_or("tsumogiri without a draw".to_string())
+    };
+    match discards.last() {
+        Some(Value::Number(code)) => match code.as_u64().unwrap_or(0) as u32 {
+            TSUMOGIRI => last_draw(),
+            code => Ok(code),
+        },
+        Some(Value::String(text)) if text.starts_with('r') => match text[1..].parse::<u32>() {
+            Ok(TSUMOGIRI) => last_draw(),
+            Ok(code) => Ok(code),
+            Err(_) => Err(format!("bad riichi '{}'", text)),
+        },
+        Some(Value::String(text)) => {
+            let (call, added_at) = Call::parse(text)?;
+            Ok(call.codes[added_at])
+        }
+        _ => Err("discarder has no discards".to_string()),
+    }
+}
+
+// exact code first, then the other five of the same suit
+fn remove_code(hand: &mut Vec<u32>, code: u32) -> Result<(), String> {
+    let position = hand
+        .iter()
+        .position(|c| *c == code)
+        .or_else(|| {
+            let plain = tile(code).ok().map(Hai::plain);
+            hand.iter()
+                .position(|c| tile(*c).ok().map(Hai::plain) == plain)
+        })
+        .ok_or_else(|| format!("tile {} is not in hand", code))?;
+    hand.remove(position);
+    Ok(())
+}
+
+// Yaku and score text
+
+type Setter = fn(UserInputBuilder) -> UserInputBuilder;
+
+fn apply_situational_yaku(mut builder: UserInputBuilder, yaku: &[&str]) -> UserInputBuilder {
+    let setters: [(&str, Setter); 10] = [
+        ("立直", UserInputBuilder::riichi),
+        ("両立直", UserInputBuilder::double_riichi),
+        ("一発", UserInputBuilder::ippatsu),
+        ("槍槓", UserInputBuilder::chankan),
+        ("嶺上開花", UserInputBuilder::rinshan),
+        ("海底摸月", UserInputBuilder::haitei),
+        ("河底撈魚", UserInputBuilder::houtei),
+        ("人和", UserInputBuilder::renhou),
+        ("天和", UserInputBuilder::tenhou),
+        ("地和", UserInputBuilder::chiihou),
+    ];
+    let names: Vec<&str> = yaku
+        .iter()
+        .map(|entry| entry.split('(').next().unwrap_or(""))
+        .collect();
+    for (name, set) in setters {
+        if names.contains(&name) {
+            builder = set(builder);
+        }
+    }
+    builder
+}
+
+// "立直(1飜)" -> 1, "国士無双(役満)" -> 13
+fn yaku_han(entry: &str) -> u32 {
+    let value = entry
+        .split_once('(')
+        .map(|(_, rest)| rest.trim_end_matches(')'))
+        .unwrap_or("");
+    match value {
+        "役満" => 13,
+        "ダブル役満" => 26,
+        _ => value.trim_end_matches('飜').parse().unwrap_or(0),
+    }
+}
+
+// "30符2飜500-1000点" -> 30; limit hands carry no fu
+fn fu_of(description: &str) -> Option<u8> {
+    let (before, _) = description.split_once('符')?;
+    before.parse().ok()
+}
+
+// hand value without honba: "1000点", "500-1000点" (ko-oya), "2600点∀" (each)
+fn points_of(description: &str) -> Result<u32, String> {
+    let bad = || format!("cannot read points from '{}'", description);
+    let (before, after) = description.split_once('点').ok_or_else(bad)?;
+    let digits: String = before
+        .chars()
+        .rev()
+        .take_while(|c| c.is_ascii_digit() || *c == '-')
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    let parts: Vec<u32> = digits
+        .split('-')
+        .map(|part| part.parse().map_err(|_| bad()))
+        .collect::<Result<_, _>>()?;
+    Ok(match (parts.as_slice(), after.contains('∀')) {
+        ([ko, oya], _) => ko * 2 + oya,
+        ([each], true) => each * 3,
+        ([total], false) => *total,
+        _ => return Err(bad()),
+    })
+}
+
+// Tiles
+
+fn tile(code: u32) -> Result<Hai, String> {
+    let index = match code {
+        11..=19 | 21..=29 | 31..=39 => (code / 10 - 1) * 9 + code % 10 - 1,
+        41..=47 => 27 + code - 41,
+        51..=53 => return Ok(index_to_tile(((code - 51) * 9 + 4) as usize).red()),
+        _ => return Err(format!("tile code {} is out of range", code)),
+    };
+    Ok(index_to_tile(index as usize))
+}
+
+fn tiles(codes: &[u32]) -> Result<Vec<Hai>, String> {
+    codes.iter().map(|code| tile(*code)).collect()
+}
+
+fn code(tile: &Hai) -> u32 {
+    match tile {
+        Hai::Suhai(Suhai { suit, .. }) if tile.is_red() => 51 + *suit as u32,
+        Hai::Suhai(Suhai { number, suit, .. }) => (*suit as u32 + 1) * 10 + *number as u32,
+        Hai::Jihai(_) => 41 + tile_to_index(tile) as u32 - 27,
+    }
+}
+
+fn codes(value: &Value) -> Result<Vec<u32>, String> {
+    Ok(numbers(value)?
+        .into_iter()
+        .map(|code| code as u32)
+        .collect())
+}
+
+fn numbers(value: &Value) -> Result<Vec<i64>, String> {
+    let Some(list) = value.as_array() else {
+        return Ok(Vec::new());
+    };
+    list.iter()
+        .map(|entry| {
+            entry
+                .as_i64()
+                .ok_or_else(|| format!("{} is not a number", entry))
+        })
+        .collect()
+}
+
+// Export
+
+// scored hands as a tenhou.net/6 log, one round per hand
+//
+// Only the winning hand, its calls, dora and the result are written; the
+// other players' tiles are not recorded by the engine and stay empty. The
+// dealer is written as player 0, so seats are counted from the dealer.
+pub fn export_log(hands: &[(UserInput, AgariResult)], rules: &RuleSet) -> String {
+    let rounds: Vec<Value> = hands
+        .iter()
+        .map(|(input, result)| export_round(input, result, rules))
+        .collect();
+    json!({
+        "title": ["riichi-mahjong-calculator", ""],
+        "name": ["East", "South", "West", "North"],
+        "rule": { "disp": "般南喰赤", "aka": 1 },
+        "log": rounds,
+    })
+    .to_string()
+}
+
+fn export_round(input: &UserInput, result: &AgariResult, rules: &RuleSet) -> Value {
+    let game = &input.game_context;
+    let player_of = |seat: Kaze| (seat as usize + 4 - game.oya_kaze as usize) % 4;
+    let winner = player_of(input.player_context.jikaze);
+
+    // winner: hand before the win, calls as takes, kans as discards
+    let mut concealed = input.hand_tiles.clone();
+    if input.agari_type == AgariType::Tsumo
+        && let Some(pos) = concealed.iter().position(|t| *t == input.winning_tile)
+    {
+        concealed.remove(pos);
+    }
+    let mut hai: Vec<u32> = concealed.iter().map(code).collect();
+    let mut takes: Vec<Value> = Vec::new();
+    let mut discards: Vec<Value> = Vec::new();
+
+    for meld in &input.open_melds {
+        let Ok(meld_tiles) = meld.tiles() else {
+            continue;
+        };
+        let codes: Vec<u32> = meld_tiles.iter().map(code).collect();
+        let text = |letter: char, codes: &[u32]| {
+            let rest: String = codes[1..].iter().map(u32::to_string).collect();
+            format!("{}{}{}", letter, codes[0], rest)
+        };
+        match (meld.mentsu_type, meld.is_added_kan) {
+            (MentsuType::Kantsu, true) => {
+                // pon first, then draw and add the fourth tile
+                hai.extend(&codes[1..3]);
+                takes.push(json!(text('p', &codes[..3])));
+                discards.push(json!(0));
+                takes.push(json!(codes[3]));
+                discards.push(json!(format!(
+                    "k{}{}{}{}",
+                    codes[3], codes[0], codes[1], codes[2]
+                )));
+            }
+            (MentsuType::Kantsu, false) => {
+                hai.extend(&codes[1..]);
+                takes.push(json!(text('m', &codes)));
+                discards.push(json!(0));
+            }
+            (MentsuType::Koutsu, _) => {
+                hai.extend(&codes[1..]);
+                takes.push(json!(text('p', &codes)));
+                discards.push(json!(0));
+            }
+            (MentsuType::Shuntsu, _) => {
+                hai.extend(&codes[1..]);
+                takes.push(json!(text('c', &codes)));
+                discards.push(json!(0));
+            }
+        }
+    }
+    for kan in &input.closed_kans {
+        let Ok(kan_tiles) = kan.tiles() else {
+            continue;
+        };
+        let codes: Vec<u32> = kan_tiles.iter().map(code).collect();
+        hai.extend(&codes[..3]);
+        takes.push(json!(codes[3]));
+        let text: String = codes[..3].iter().map(u32::to_string).collect();
+        discards.push(json!(format!("{}a{}", text, codes[3])));
+    }
+    let winning_code = code(&input.winning_tile);
+    if input.agari_type == AgariType::Tsumo {
+        takes.push(json!(winning_code));
+    }
+
+    let mut players = vec![(json!([]), json!([]), json!([])); 4];
+    players[winner] = (json!(hai), json!(takes), json!(discards));
+    let from = match (input.agari_type, game.discarder) {
+        (AgariType::Ron, Some(discarder)) => {
+            let from = player_of(discarder);
+            players[from].2 = json!([winning_code]);
+            from
+        }
+        _ => winner,
+    };
+
+    let mut deltas = [0i32; 4];
+    for seat in [Kaze::Ton, Kaze::Nan, Kaze::Shaa, Kaze::Pei] {
+        deltas[player_of(seat)] = result.point_delta(seat).unwrap_or(0);
+    }
+    let mut info = vec![
+        json!(winner),
+        json!(from),
+        json!(winner),
+        json!(describe(result, rules)),
+    ];
+    info.extend(
+        yaku_entries(input, result, rules)
+            .into_iter()
+            .map(Value::from),
+    );
+
+    let kyoku = game.bakaze as u32 * 4;
+    let mut round = vec![
+        json!([kyoku, game.honba, game.kyoutaku]),
+        json!([25000, 25000, 25000, 25000]),
+        json!(game.dora_indicators.iter().map(code).collect::<Vec<_>>()),
+        json!(game.uradora_indicators.iter().map(code).collect::<Vec<_>>()),
+    ];
+    for (hai, takes, discards) in players {
+        round.extend([hai, takes, discards]);
+    }
+    round.push(json!([AGARI, deltas, info]));
+    Value::Array(round)
+}
+
+// "30符2飜500-1000点", "満貫2000-4000点", "役満16000点∀"
+fn describe(result: &AgariResult, rules: &RuleSet) -> String {
+    let honba = result.honba as u32;
+    let points = match (result.agari_type, result.is_oya) {
+        (AgariType::Tsumo, true) => format!("{}点∀", result.oya_payment),
+        (AgariType::Tsumo, false) => format!("{}-{}点", result.ko_payment, result.oya_payment),
+        (AgariType::Ron, _) => format!("{}点", result.total_payment - honba * rules.honba_value),
+    };
+    let prefix = match &result.limit_name {
+        None => format!("{}符{}飜", result.fu, result.han),
+        Some(HandLimit::Mangan) => "満貫".to_string(),
+        Some(HandLimit::Haneman) => "跳満".to_string(),
+        Some(HandLimit::Baiman) => "倍満".to_string(),
+        Some(HandLimit::Sanbaiman) => "三倍満".to_string(),
+        Some(HandLimit::Yakuman) => "役満".to_string(),
+    };
+    format!("{}{}", prefix, points)
+}
+
+// "立直(1飜)", "ドラ(2飜)", "国士無双(役満)"
+fn yaku_entries(input: &UserInput, result: &AgariResult, rules: &RuleSet) -> Vec<String> {
+    let is_menzen = input.is_menzen();
+    let mut entries: Vec<(Yaku, u32)> = Vec::new();
+    for yaku in &result.yaku_list {
+        match entries.iter_mut().find(|(seen, _)| seen == yaku) {
+            Some((_, count)) => *count += 1,
+            None => entries.push((*yaku, 1)),
+        }
+    }
+    entries
+        .into_iter()
+        .map(|(yaku, count)| {
+            let name = yaku_name(&yaku, input);
+            match count_yakuman(&[yaku], rules) {
+                0 => format!(
+                    "{}({}飜)",
+                    name,
+                    get_han_value(&yaku, is_menzen, rules) as u32 * count
+                ),
+                1 => format!("{}(役満)", name),
+                _ => format!("{}(ダブル役満)", name),
+            }
+        })
+        .collect()
+}
+
+fn yaku_name(yaku: &Yaku, input: &UserInput) -> String {
+    let wind = |kaze: Kaze| match kaze {
+        Kaze::Ton => "東",
+        Kaze::Nan => "南",
+        Kaze::Shaa => "西",
+        Kaze::Pei => "北",
+    };
+    let dragon = |sangen: Sangenpai| match sangen {
+        Sangenpai::Haku => "白",
+        Sangenpai::Hatsu => "發",
+        Sangenpai::Chun => "中",
+    };
+    let name = match yaku {
+        Yaku::MenzenTsumo => "門前清自摸和",
+        Yaku::Riichi => "立直",
+        Yaku::Ippatsu => "一発",
+        Yaku::Chankan => "槍槓",
+        Yaku::RinshanKaihou => "嶺上開花",
+        Yaku::HaiteiRaoyue => "海底摸月",
+        Yaku::HouteiRaoyui => "河底撈魚",
+        Yaku::Pinfu => "平和",
+        Yaku::Tanyao => "断幺九",
+        Yaku::Iipeikou => "一盃口",
+        Yaku::YakuhaiJikaze => return format!("自風 {}", wind(input.player_context.jikaze)),
+        Yaku::YakuhaiBakaze => return format!("場風 {}", wind(input.game_context.bakaze)),
+        Yaku::YakuhaiHaku => return format!("役牌 {}", dragon(Sangenpai::Haku)),
+        Yaku::YakuhaiHatsu => return format!("役牌 {}", dragon(Sangenpai::Hatsu)),
+        Yaku::YakuhaiChun => return format!("役牌 {}", dragon(Sangenpai::Chun)),
+        Yaku::DaburuRiichi => "両立直",
+        Yaku::Chiitoitsu => "七対子",
+        Yaku::Chanta => "混全帯幺九",
+        Yaku::Ittsu => "一気通貫",
+        Yaku::SanshokuDoujun => "三色同順",
+        Yaku::SanshokuDoukou => "三色同刻",
+        Yaku::Sankantsu => "三槓子",
+        Yaku::Toitoi => "対々和",
+        Yaku::Sanankou => "三暗刻",
+        Yaku::Shousangen => "小三元",
+        Yaku::Honroutou => "混老頭",
+        Yaku::Ryanpeikou => "二盃口",
+        Yaku::Junchan => "純全帯幺九",
+        Yaku::Honitsu => "混一色",
+        Yaku::Chinitsu => "清一色",
+        Yaku::Renhou => "人和",
+        Yaku::Tenhou => "天和",
+        Yaku::Chiihou => "地和",
+        Yaku::Daisangen => "大三元",
+        Yaku::Suuankou => "四暗刻",
+        Yaku::SuuankouTanki => "四暗刻単騎",
+        Yaku::Tsuuiisou => "字一色",
+        Yaku::Ryuuiisou => "緑一色",
+        Yaku::Chinroutou => "清老頭",
+        Yaku::ChuurenPoutou => "九蓮宝燈",
+        Yaku::JunseiChuurenPoutou => "純正九蓮宝燈",
+        Yaku::KokushiMusou => "国士無双",
+        Yaku::KokushiMusouJusanmen => "国士無双１３面",
+        Yaku::Daisuushi => "大四喜",
+        Yaku::Shousuushi => "小四喜",
+        Yaku::Suukantsu => "四槓子",
+        Yaku::Dora => "ドラ",
+        Yaku::UraDora => "裏ドラ",
+        Yaku::AkaDora => "赤ドラ",
+        Yaku::OpenRiichi => "オープン立直",
+        Yaku::OpenRiichiDealIn => "オープン立直放銃",
+        Yaku::NukiDora => "抜きドラ",
+    };
+    name.to_string()
+}
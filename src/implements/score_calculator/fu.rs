@@ -1,20 +1,21 @@
 use crate::implements::types::{
     game::{AgariType, GameContext, PlayerContext},
     hand::{HandStructure, Machi, MentsuType},
+    rules::RuleSet,
     scoring::{
-        FU_BASE, FU_CHIITOITSU, FU_MENZEN_RON, FU_PAIR_DRAGON, FU_PAIR_SINGLE_WAIT, FU_PAIR_WIND,
-        FU_PINFU_RON, FU_PINFU_TSUMO, FU_ROUND_UP, FU_TSUMO,
+        FU_BASE, FU_MENZEN_RON, FU_OPEN_PINFU, FU_PAIR_DRAGON, FU_PAIR_SINGLE_WAIT, FU_PAIR_WIND,
+        FU_PINFU_TSUMO, FU_ROUND_UP, FU_TSUMO, FuEntry, FuKind,
     },
     tiles::{Hai, Jihai},
     yaku::Yaku,
 };
 
-const FU_TABLE_KOUTSU: [[u32; 2]; 2] = [
+const FU_TABLE_KOUTSU: [[u8; 2]; 2] = [
     [4, 8], // Closed: simple, terminal
     [2, 4], // Open: simple, terminal
 ];
 
-const FU_TABLE_KANTSU: [[u32; 2]; 2] = [
+const FU_TABLE_KANTSU: [[u8; 2]; 2] = [
     [16, 32], // Closed: simple, terminal
     [8, 16],  // Open: simple, terminal
 ];
@@ -25,36 +26,46 @@ pub fn calculate_fu(
     player: &PlayerContext,
     game: &GameContext,
     agari_type: AgariType,
-) -> u8 {
+    rules: &RuleSet,
+) -> (u8, Vec<FuEntry>) {
     // Chiitoitsu
     if yaku_list.contains(&Yaku::Chiitoitsu) {
-        return FU_CHIITOITSU;
+        return flat_fu(FuKind::Chiitoitsu, rules.chiitoitsu_fu);
     }
 
     // Pinfu
-    if yaku_list.contains(&Yaku::Pinfu) {
-        return if agari_type == AgariType::Tsumo {
-            FU_PINFU_TSUMO
-        } else {
-            FU_PINFU_RON
-        };
+    if yaku_list.contains(&Yaku::Pinfu) && agari_type == AgariType::Tsumo {
+        return flat_fu(FuKind::PinfuTsumo, FU_PINFU_TSUMO);
     }
 
     // Standard Fu
-    let mut fu = FU_BASE as u32;
-
     let hand = match hand_structure {
         HandStructure::YonmentsuIchiatama(h) => h,
         HandStructure::ChuurenPoutou { hand, .. } => hand,
-        HandStructure::Chiitoitsu { .. } => return FU_CHIITOITSU,
-        HandStructure::KokushiMusou { .. } => return 0,
+        HandStructure::Chiitoitsu { .. } => {
+            return flat_fu(FuKind::Chiitoitsu, rules.chiitoitsu_fu);
+        }
+        HandStructure::KokushiMusou { .. } => return (0, Vec::new()),
     };
 
-    // Agari Type
+    let mut entries = vec![FuEntry {
+        kind: FuKind::Base,
+        fu: FU_BASE,
+    }];
+
+    // Agari Type (嶺上開花 may go without the tsumo fu)
     if agari_type == AgariType::Tsumo {
-        fu += FU_TSUMO as u32;
+        if !game.is_rinshan || rules.rinshan_tsumo_fu {
+            entries.push(FuEntry {
+                kind: FuKind::Tsumo,
+                fu: FU_TSUMO,
+            });
+        }
     } else if player.is_menzen {
-        fu += FU_MENZEN_RON as u32;
+        entries.push(FuEntry {
+            kind: FuKind::MenzenRon,
+            fu: FU_MENZEN_RON,
+        });
     }
 
     // Melds
@@ -62,45 +73,253 @@ pub fn calculate_fu(
         let is_open = mentsu.is_minchou;
         let is_yaochuu = mentsu.tiles[0].is_yaochuu();
 
-        match mentsu.mentsu_type {
-            MentsuType::Koutsu => {
-                fu += FU_TABLE_KOUTSU[is_open as usize][is_yaochuu as usize];
-            }
-            MentsuType::Kantsu => {
-                fu += FU_TABLE_KANTSU[is_open as usize][is_yaochuu as usize];
-            }
-            MentsuType::Shuntsu => {}
-        }
+        let fu = match mentsu.mentsu_type {
+            MentsuType::Koutsu => FU_TABLE_KOUTSU[is_open as usize][is_yaochuu as usize],
+            MentsuType::Kantsu => FU_TABLE_KANTSU[is_open as usize][is_yaochuu as usize],
+            MentsuType::Shuntsu => continue,
+        };
+        entries.push(FuEntry {
+            kind: FuKind::Mentsu {
+                mentsu_type: mentsu.mentsu_type,
+                is_open,
+                is_yaochuu,
+            },
+            fu,
+        });
     }
 
     // Pair
-    fu += get_pair_fu(&hand.atama.0, player, game);
+    let pair_fu = get_pair_fu(&hand.atama.0, player, game, rules);
+    if pair_fu > 0 {
+        entries.push(FuEntry {
+            kind: FuKind::Pair(hand.atama.0),
+            fu: pair_fu,
+        });
+    }
 
     // Wait
-    match hand.machi {
-        Machi::Kanchan | Machi::Penchan | Machi::Tanki => fu += FU_PAIR_SINGLE_WAIT as u32,
-        _ => {}
+    if let Machi::Kanchan | Machi::Penchan | Machi::Tanki = hand.machi {
+        entries.push(FuEntry {
+            kind: FuKind::Wait(hand.machi),
+            fu: FU_PAIR_SINGLE_WAIT,
+        });
     }
 
-    let round_up = FU_ROUND_UP as u32;
-    (((fu + round_up - 1) / round_up) * round_up) as u8
+    let fu: u8 = entries.iter().map(|entry| entry.fu).sum();
+
+    // 喰い平和形: open hand with no fu sources scores 30, not 20
+    let fu = if !player.is_menzen && agari_type == AgariType::Ron && fu == FU_BASE {
+        entries.push(FuEntry {
+            kind: FuKind::OpenPinfu,
+            fu: FU_OPEN_PINFU,
+        });
+        fu + FU_OPEN_PINFU
+    } else {
+        fu
+    };
+
+    let rounded = fu.div_ceil(FU_ROUND_UP) * FU_ROUND_UP;
+    if rounded > fu {
+        entries.push(FuEntry {
+            kind: FuKind::RoundUp,
+            fu: rounded - fu,
+        });
+    }
+
+    (rounded, entries)
+}
+
+fn flat_fu(kind: FuKind, fu: u8) -> (u8, Vec<FuEntry>) {
+    (fu, vec![FuEntry { kind, fu }])
 }
 
-fn get_pair_fu(tile: &Hai, player: &PlayerContext, game: &GameContext) -> u32 {
+fn get_pair_fu(tile: &Hai, player: &PlayerContext, game: &GameContext, rules: &RuleSet) -> u8 {
     match tile {
         // Dragon Pair
         Hai::Jihai(Jihai::Sangen(_)) => FU_PAIR_DRAGON,
-        // Wind Pair
-        Hai::Jihai(Jihai::Kaze(k)) => {
-            let mut fu = 0;
-            if *k == game.bakaze {
-                fu += FU_PAIR_WIND;
-            }
-            if *k == player.jikaze {
-                fu += FU_PAIR_WIND;
-            }
-            fu
+        // Wind Pair (連風牌 by rule)
+        Hai::Jihai(Jihai::Kaze(k)) if *k == game.bakaze && *k == player.jikaze => {
+            rules.double_wind_pair_fu
         }
+        Hai::Jihai(Jihai::Kaze(k)) if *k == game.bakaze || *k == player.jikaze => FU_PAIR_WIND,
         _ => 0,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::implements::types::{
+        hand::{Machi, MentsuType},
+        input::UserInput,
+        rules::RuleSet,
+        scoring::{FuEntry, FuKind},
+        tiles::{Hai, Kaze},
+    };
+    use crate::implements::{calculate_agari, parse_hand};
+
+    fn tile(text: &str) -> Hai {
+        text.parse().unwrap()
+    }
+
+    fn fu_of(input: &UserInput, rules: &RuleSet) -> (u8, Vec<FuEntry>) {
+        let result = calculate_agari(input, rules).unwrap();
+        (result.fu, result.fu_breakdown)
+    }
+
+    fn entry(kind: FuKind, fu: u8) -> FuEntry {
+        FuEntry { kind, fu }
+    }
+
+    fn closed_koutsu(is_yaochuu: bool) -> FuKind {
+        FuKind::Mentsu {
+            mentsu_type: MentsuType::Koutsu,
+            is_open: false,
+            is_yaochuu,
+        }
+    }
+
+    // 喰い平和形: open tanyao on a ryanmen ron, no fu sources, 30 fu
+    #[test]
+    fn open_pinfu_shape_ron_is_30_fu() {
+        let input = parse_hand("234m67p22s 678s+ 345s+ 5p")
+            .unwrap()
+            .ron(Kaze::Nan)
+            .build()
+            .unwrap();
+        let (fu, breakdown) = fu_of(&input, &RuleSet::default());
+        assert_eq!(fu, 30);
+        assert_eq!(
+            breakdown,
+            vec![entry(FuKind::Base, 20), entry(FuKind::OpenPinfu, 10),]
+        );
+    }
+
+    // 嶺上開花 off a closed kan, 2222m ankan and a closed 444p
+    #[test]
+    fn rinshan_tsumo_fu_follows_the_rule() {
+        let input = parse_hand("444p678s11s45s 2222m# 3s")
+            .unwrap()
+            .tsumo()
+            .rinshan()
+            .build()
+            .unwrap();
+        let kan = entry(
+            FuKind::Mentsu {
+                mentsu_type: MentsuType::Kantsu,
+                is_open: false,
+                is_yaochuu: false,
+            },
+            16,
+        );
+
+        let (fu, breakdown) = fu_of(&input, &RuleSet::default());
+        assert_eq!(fu, 50);
+        assert_eq!(
+            breakdown,
+            vec![
+                entry(FuKind::Base, 20),
+                entry(FuKind::Tsumo, 2),
+                kan,
+                entry(closed_koutsu(false), 4),
+                entry(FuKind::RoundUp, 8),
+            ]
+        );
+
+        let rules = RuleSet {
+            rinshan_tsumo_fu: false,
+            ..RuleSet::default()
+        };
+        let (fu, breakdown) = fu_of(&input, &rules);
+        assert_eq!(fu, 40);
+        assert_eq!(
+            breakdown,
+            vec![entry(FuKind::Base, 20), kan, entry(closed_koutsu(false), 4)]
+        );
+    }
+
+    // 連風牌: East pair for the East seat in the East round
+    #[test]
+    fn double_wind_pair_fu_follows_the_rule() {
+        let input = parse_hand("111m999p11z234s56s 7s")
+            .unwrap()
+            .tsumo()
+            .build()
+            .unwrap();
+        let east = tile("1z");
+
+        let (fu, breakdown) = fu_of(&input, &RuleSet::default());
+        assert_eq!(fu, 50);
+        assert_eq!(
+            breakdown,
+            vec![
+                entry(FuKind::Base, 20),
+                entry(FuKind::Tsumo, 2),
+                entry(closed_koutsu(true), 8),
+                entry(closed_koutsu(true), 8),
+                entry(FuKind::Pair(east), 4),
+                entry(FuKind::RoundUp, 8),
+            ]
+        );
+
+        let rules = RuleSet {
+            double_wind_pair_fu: 2,
+            ..RuleSet::default()
+        };
+        let (fu, breakdown) = fu_of(&input, &rules);
+        assert_eq!(fu, 40);
+        assert_eq!(
+            breakdown,
+            vec![
+                entry(FuKind::Base, 20),
+                entry(FuKind::Tsumo, 2),
+                entry(closed_koutsu(true), 8),
+                entry(closed_koutsu(true), 8),
+                entry(FuKind::Pair(east), 2),
+            ]
+        );
+    }
+
+    // 七対子: flat 25 fu, or 50 under the rule
+    #[test]
+    fn chiitoitsu_fu_follows_the_rule() {
+        let input = parse_hand("113355m77p2266s4z 4z")
+            .unwrap()
+            .ron(Kaze::Shaa)
+            .build()
+            .unwrap();
+
+        let (fu, breakdown) = fu_of(&input, &RuleSet::default());
+        assert_eq!(fu, 25);
+        assert_eq!(breakdown, vec![entry(FuKind::Chiitoitsu, 25)]);
+
+        let rules = RuleSet {
+            chiitoitsu_fu: 50,
+            ..RuleSet::default()
+        };
+        let (fu, breakdown) = fu_of(&input, &rules);
+        assert_eq!(fu, 50);
+        assert_eq!(breakdown, vec![entry(FuKind::Chiitoitsu, 50)]);
+    }
+
+    // 嵌張 on a closed hand: 20 + 10 menzen ron + 2 wait, rounded to 40
+    #[test]
+    fn kanchan_ron_breakdown() {
+        let input = parse_hand("123m567p22s678s79p 8p")
+            .unwrap()
+            .riichi()
+            .ron(Kaze::Nan)
+            .build()
+            .unwrap();
+        let (fu, breakdown) = fu_of(&input, &RuleSet::default());
+        assert_eq!(fu, 40);
+        assert_eq!(
+            breakdown,
+            vec![
+                entry(FuKind::Base, 20),
+                entry(FuKind::MenzenRon, 10),
+                entry(FuKind::Wait(Machi::Kanchan), 2),
+                entry(FuKind::RoundUp, 8),
+            ]
+        );
+    }
+}
@@ -1,13 +1,27 @@
-use crate::implements::types::yaku::Yaku;
+use crate::implements::types::{
+    rules::{RenhouValue, RuleSet},
+    yaku::Yaku,
+};
 
-pub fn calculate_han(yaku_list: &[Yaku], is_menzen: bool) -> u8 {
-    yaku_list
+pub fn calculate_han(yaku_list: &[Yaku], is_menzen: bool, rules: &RuleSet) -> u8 {
+    let han: u8 = yaku_list
         .iter()
-        .map(|yaku| get_han_value(yaku, is_menzen))
-        .sum()
+        .filter(|yaku| **yaku != Yaku::Renhou)
+        .map(|yaku| get_han_value(yaku, is_menzen, rules))
+        .sum();
+
+    // 人和: a limit stands in for the other yaku, 5 han adds to them
+    if !yaku_list.contains(&Yaku::Renhou) {
+        return han;
+    }
+    let renhou = get_han_value(&Yaku::Renhou, is_menzen, rules);
+    match rules.renhou {
+        RenhouValue::FiveHan => han + renhou,
+        _ => han.max(renhou),
+    }
 }
 
-fn get_han_value(yaku: &Yaku, is_menzen: bool) -> u8 {
+pub fn get_han_value(yaku: &Yaku, is_menzen: bool, rules: &RuleSet) -> u8 {
     match yaku {
         // 1 Han
         Yaku::Riichi => 1,
@@ -19,6 +33,7 @@ fn get_han_value(yaku: &Yaku, is_menzen: bool) -> u8 {
         Yaku::HouteiRaoyui => 1,
         Yaku::RinshanKaihou => 1,
         Yaku::Chankan => 1,
+        Yaku::OpenRiichi => 1, // on top of Riichi or DaburuRiichi
         Yaku::Tanyao => 1,
         Yaku::YakuhaiJikaze => 1,
         Yaku::YakuhaiBakaze => 1,
@@ -86,10 +101,18 @@ fn get_han_value(yaku: &Yaku, is_menzen: bool) -> u8 {
             }
         }
 
+        // Renhou (by rule)
+        Yaku::Renhou => match rules.renhou {
+            RenhouValue::Mangan | RenhouValue::FiveHan => 5,
+            RenhouValue::Baiman => 8,
+            RenhouValue::Off | RenhouValue::Yakuman => 0,
+        },
+
         // Dora
         Yaku::Dora => 1,
         Yaku::UraDora => 1,
         Yaku::AkaDora => 1,
+        Yaku::NukiDora => 1,
 
         _ => 0,
     }
@@ -6,12 +6,13 @@ pub mod yakuman;
 use self::{
     fu::calculate_fu,
     han::calculate_han,
-    points::{calculate_basic_points, round_up_100},
+    points::{calculate_basic_points, calculate_payments, calculate_point_deltas},
     yakuman::count_yakuman,
 };
 use super::YakuResult;
 use crate::implements::types::{
     game::{AgariType, GameContext, PlayerContext},
+    rules::RuleSet,
     scoring::{AgariResult, HandLimit},
 };
 
@@ -20,113 +21,92 @@ pub fn calculate_score(
     player: &PlayerContext,
     game: &GameContext,
     agari_type: AgariType,
+    rules: &RuleSet,
 ) -> AgariResult {
-    let tsumo_bonus = game.honba as u32 * 100;
-    let ron_bonus = game.honba as u32 * 300;
     let yaku_list = yaku_result.yaku_list;
     let num_akadora = yaku_result.num_akadora;
+    let machi = yaku_result.hand_structure.machi();
 
     // Check Yakuman
-    let num_yakuman = count_yakuman(&yaku_list);
+    let num_yakuman = count_yakuman(&yaku_list, rules);
 
-    let fu = calculate_fu(
+    let (fu, fu_breakdown) = calculate_fu(
         &yaku_result.hand_structure,
         &yaku_list,
         player,
         game,
         agari_type,
+        rules,
     );
 
     if num_yakuman > 0 {
         // Yakuman
-        let extra_han = calculate_han(&yaku_list, player.is_menzen);
+        let extra_han = calculate_han(&yaku_list, player.is_menzen, rules);
         let han = 13 * num_yakuman as u8 + extra_han;
         let limit_name = Some(HandLimit::Yakuman);
-        let base_yakuman_points = (8000 * num_yakuman) as u32;
+        let base_yakuman_points = 8000 * num_yakuman;
 
-        let (oya_payment, ko_payment, total_payment) = match (player.is_oya, agari_type) {
-            // Oya Tsumo
-            (true, AgariType::Tsumo) => {
-                let p = round_up_100(base_yakuman_points * 2);
-                let total = (p + tsumo_bonus) * 3;
-                (p, 0, total)
-            }
-            // Ko Tsumo
-            (false, AgariType::Tsumo) => {
-                let oya_p = round_up_100(base_yakuman_points * 2);
-                let ko_p = round_up_100(base_yakuman_points * 1);
-                let total = (oya_p + tsumo_bonus) + (ko_p + tsumo_bonus) * 2;
-                (oya_p, ko_p, total)
-            }
-            // Oya Ron
-            (true, AgariType::Ron) => {
-                let total = round_up_100(base_yakuman_points * 6) + ron_bonus;
-                (0, 0, total)
-            }
-            // Ko Ron
-            (false, AgariType::Ron) => {
-                let total = round_up_100(base_yakuman_points * 4) + ron_bonus;
-                (0, 0, total)
-            }
-        };
+        let (oya_payment, ko_payment, total_payment) =
+            calculate_payments(base_yakuman_points, player.is_oya, agari_type, game, rules);
 
         return AgariResult {
+            hand_structure: yaku_result.hand_structure,
+            machi,
             han,
             fu,
+            fu_breakdown,
             yaku_list,
             num_akadora: 0,
+            num_nukidora: 0,
             limit_name,
             oya_payment,
             ko_payment,
             total_payment,
             honba: game.honba,
+            kyoutaku: game.kyoutaku,
             agari_type,
             is_oya: player.is_oya,
+            point_deltas: calculate_point_deltas(
+                player,
+                game,
+                agari_type,
+                (oya_payment, ko_payment, total_payment),
+                rules,
+            ),
         };
     }
 
     // Regular Hand
-    let han = calculate_han(&yaku_list, player.is_menzen);
+    let han = calculate_han(&yaku_list, player.is_menzen, rules);
 
-    let (basic_points, limit_name) = calculate_basic_points(han, fu);
+    let (basic_points, limit_name) = calculate_basic_points(han, fu, rules);
 
-    let (oya_payment, ko_payment, total_payment) = match (player.is_oya, agari_type) {
-        // Oya Tsumo
-        (true, AgariType::Tsumo) => {
-            let p = round_up_100(basic_points * 2);
-            let total = (p + tsumo_bonus) * 3;
-            (p, 0, total)
-        }
-        // Ko Tsumo
-        (false, AgariType::Tsumo) => {
-            let oya_p = round_up_100(basic_points * 2);
-            let ko_p = round_up_100(basic_points * 1);
-            let total = (oya_p + tsumo_bonus) + (ko_p + tsumo_bonus) * 2;
-            (oya_p, ko_p, total)
-        }
-        // Oya Ron
-        (true, AgariType::Ron) => {
-            let total = round_up_100(basic_points * 6) + ron_bonus;
-            (0, 0, total)
-        }
-        // Ko Ron
-        (false, AgariType::Ron) => {
-            let total = round_up_100(basic_points * 4) + ron_bonus;
-            (0, 0, total)
-        }
-    };
+    let (oya_payment, ko_payment, total_payment) =
+        calculate_payments(basic_points, player.is_oya, agari_type, game, rules);
 
     AgariResult {
+        hand_structure: yaku_result.hand_structure,
+        machi,
         han,
         fu,
+        fu_breakdown,
         yaku_list,
         num_akadora,
+        num_nukidora: yaku_result.num_nukidora,
         limit_name,
         oya_payment,
         ko_payment,
         total_payment,
         honba: game.honba,
+        kyoutaku: game.kyoutaku,
         agari_type,
         is_oya: player.is_oya,
+        point_deltas: calculate_point_deltas(
+            player,
+            game,
+            agari_type,
+            (oya_payment, ko_payment, total_payment),
+            rules,
+        ),
     }
 }
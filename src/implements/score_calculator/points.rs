@@ -1,7 +1,13 @@
-use crate::implements::types::scoring::HandLimit;
+use crate::implements::types::{
+    game::{AgariType, GameContext, PlayerContext},
+    rules::{RuleSet, SanmaTsumo},
+    scoring::HandLimit,
+    tiles::Kaze,
+};
 
-pub fn calculate_basic_points(han: u8, fu: u8) -> (u32, Option<HandLimit>) {
-    if han >= 13 {
+pub fn calculate_basic_points(han: u8, fu: u8, rules: &RuleSet) -> (u32, Option<HandLimit>) {
+    // 数え役満
+    if han >= 13 && rules.kazoe_yakuman {
         return (8000, Some(HandLimit::Yakuman));
     }
     if han >= 11 {
@@ -20,8 +26,8 @@ pub fn calculate_basic_points(han: u8, fu: u8) -> (u32, Option<HandLimit>) {
     // Below Mangan
     let basic_points = (fu as u32) * (1 << (han + 2));
 
-    // kiriage Mangan
-    if basic_points >= 2000 {
+    // capped at Mangan; kiriage rounds 1920 (4 han 30 fu, 3 han 60 fu) up
+    if basic_points >= 2000 || (rules.kiriage_mangan && basic_points >= 1920) {
         (2000, Some(HandLimit::Mangan))
     } else {
         (basic_points, None)
@@ -29,5 +35,270 @@ pub fn calculate_basic_points(han: u8, fu: u8) -> (u32, Option<HandLimit>) {
 }
 
 pub fn round_up_100(n: u32) -> u32 {
-    (n + 99) / 100 * 100
+    n.div_ceil(100) * 100
+}
+
+// (oya_payment, ko_payment, total_payment) for a hand worth basic_points
+pub fn calculate_payments(
+    basic_points: u32,
+    is_oya: bool,
+    agari_type: AgariType,
+    game: &GameContext,
+    rules: &RuleSet,
+) -> (u32, u32, u32) {
+    // 積み棒: ron takes the ron value, each tsumo payer the tsumo value
+    let ron_bonus = game.honba as u32 * rules.honba_value;
+    let tsumo_bonus = game.honba as u32 * rules.honba_tsumo_value;
+
+    // 三人麻雀 has no North seat; 北家折半 splits its share between the payers
+    let players = if rules.sanma { 3 } else { 4 };
+    let bisection = rules.sanma && rules.sanma_tsumo == SanmaTsumo::NorthBisection;
+
+    match (is_oya, agari_type) {
+        // Oya Tsumo
+        (true, AgariType::Tsumo) => {
+            let share = if bisection {
+                basic_points * 3
+            } else {
+                basic_points * 2
+            };
+            let p = round_up_100(share);
+            let total = (p + tsumo_bonus) * (players - 1);
+            (p, 0, total)
+        }
+        // Ko Tsumo
+        (false, AgariType::Tsumo) => {
+            let (oya_share, ko_share) = if bisection {
+                (basic_points * 5 / 2, basic_points * 3 / 2)
+            } else {
+                (basic_points * 2, basic_points)
+            };
+            let oya_p = round_up_100(oya_share);
+            let ko_p = round_up_100(ko_share);
+            let total = (oya_p + tsumo_bonus) + (ko_p + tsumo_bonus) * (players - 2);
+            (oya_p, ko_p, total)
+        }
+        // Oya Ron
+        (true, AgariType::Ron) => {
+            let total = round_up_100(basic_points * 6) + ron_bonus;
+            (0, 0, total)
+        }
+        // Ko Ron
+        (false, AgariType::Ron) => {
+            let total = round_up_100(basic_points * 4) + ron_bonus;
+            (0, 0, total)
+        }
+    }
+}
+
+// 点数移動: payers lose their share, winner collects it plus the deposits
+// (unknown for a Ron without a discarder seat)
+pub fn calculate_point_deltas(
+    player: &PlayerContext,
+    game: &GameContext,
+    agari_type: AgariType,
+    (oya_payment, ko_payment, total_payment): (u32, u32, u32),
+    rules: &RuleSet,
+) -> Option<[i32; 4]> {
+    let tsumo_bonus = game.honba as u32 * rules.honba_tsumo_value;
+    let mut deltas = [0i32; 4];
+
+    match agari_type {
+        AgariType::Tsumo => {
+            for seat in [Kaze::Ton, Kaze::Nan, Kaze::Shaa, Kaze::Pei] {
+                // 三人麻雀 has no North seat
+                if seat == player.jikaze || (rules.sanma && seat == Kaze::Pei) {
+                    continue;
+                }
+                let payment = if player.is_oya || seat == game.oya_kaze {
+                    oya_payment
+                } else {
+                    ko_payment
+                };
+                deltas[seat as usize] = -((payment + tsumo_bonus) as i32);
+            }
+        }
+        AgariType::Ron => {
+            deltas[game.discarder? as usize] = -(total_payment as i32);
+        }
+    }
+
+    let collected: i32 = -deltas.iter().sum::<i32>();
+    deltas[player.jikaze as usize] = collected + game.kyoutaku as i32 * 1000;
+    Some(deltas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::calculate_basic_points;
+    use crate::implements::calculate_agari;
+    use crate::implements::notation::parse_hand;
+    use crate::implements::types::{
+        builder::UserInputBuilder,
+        rules::{RuleSet, SanmaTsumo},
+        scoring::{AgariResult, HandLimit},
+        tiles::{Hai, Kaze},
+        yaku::Yaku,
+    };
+
+    // menzen tsumo and South's 222z, 40 fu; the dealer has only the tsumo
+    fn sanma_tsumo(seat: Kaze) -> UserInputBuilder {
+        parse_hand("123p456p789s11z22z 2z")
+            .unwrap()
+            .tsumo()
+            .seat(seat)
+    }
+
+    fn score(builder: UserInputBuilder, rules: &RuleSet) -> AgariResult {
+        calculate_agari(&builder.build().unwrap(), rules).unwrap()
+    }
+
+    fn payments(result: &AgariResult) -> (u32, u32, u32) {
+        (result.oya_payment, result.ko_payment, result.total_payment)
+    }
+
+    // closed pinfu for the South seat: 1 han 30 fu on ron, 2 han 20 fu on tsumo
+    fn pinfu() -> UserInputBuilder {
+        parse_hand("234m567p345s99s78m 6m").unwrap()
+    }
+
+    // 点数移動: the discarder pays, the winner also takes the sticks
+    #[test]
+    fn ron_deltas_collect_honba_and_sticks() {
+        let ron = pinfu().seat(Kaze::Nan).ron(Kaze::Shaa).honba(2).kyoutaku(3);
+        let result = score(ron, &RuleSet::default());
+        assert_eq!(result.total_payment, 1600);
+        assert_eq!(result.point_deltas, Some([0, 4600, -1600, 0]));
+        assert_eq!(result.point_delta(Kaze::Shaa), Some(-1600));
+    }
+
+    // every tsumo payer adds the honba
+    #[test]
+    fn tsumo_deltas_add_honba_to_every_payer() {
+        let ko = score(
+            pinfu().seat(Kaze::Nan).tsumo().honba(2).kyoutaku(1),
+            &RuleSet::default(),
+        );
+        assert_eq!(payments(&ko), (700, 400, 2100));
+        assert_eq!(ko.point_deltas, Some([-900, 3100, -600, -600]));
+
+        let oya = score(pinfu().tsumo().honba(2), &RuleSet::default());
+        assert_eq!(payments(&oya), (700, 0, 2700));
+        assert_eq!(oya.point_deltas, Some([2700, -900, -900, -900]));
+    }
+
+    // nobody is known to pay a Ron without a discarder
+    #[test]
+    fn ron_without_discarder_has_no_deltas() {
+        let mut input = pinfu().seat(Kaze::Nan).ron(Kaze::Shaa).build().unwrap();
+        input.game_context.discarder = None;
+        let result = calculate_agari(&input, &RuleSet::default()).unwrap();
+        assert_eq!(result.total_payment, 1000);
+        assert_eq!(result.point_deltas, None);
+        assert_eq!(result.point_delta(Kaze::Nan), None);
+    }
+
+    // 切り上げ満貫: 1920 rounds up to mangan
+    #[test]
+    fn kiriage_mangan_follows_the_rule() {
+        let kiriage = RuleSet {
+            kiriage_mangan: true,
+            ..RuleSet::default()
+        };
+        for (han, fu) in [(4, 30), (3, 60)] {
+            assert_eq!(
+                calculate_basic_points(han, fu, &RuleSet::default()),
+                (1920, None)
+            );
+            assert_eq!(
+                calculate_basic_points(han, fu, &kiriage),
+                (2000, Some(HandLimit::Mangan))
+            );
+        }
+        // 3 han 50 fu is 1600 either way
+        assert_eq!(calculate_basic_points(3, 50, &kiriage), (1600, None));
+    }
+
+    // 数え役満: 13 han is yakuman, or capped at sanbaiman
+    #[test]
+    fn kazoe_yakuman_follows_the_rule() {
+        let capped = RuleSet {
+            kazoe_yakuman: false,
+            ..RuleSet::default()
+        };
+        assert_eq!(
+            calculate_basic_points(13, 30, &RuleSet::default()),
+            (8000, Some(HandLimit::Yakuman))
+        );
+        assert_eq!(
+            calculate_basic_points(13, 30, &capped),
+            (6000, Some(HandLimit::Sanbaiman))
+        );
+        assert_eq!(
+            calculate_basic_points(16, 30, &capped),
+            (6000, Some(HandLimit::Sanbaiman))
+        );
+    }
+
+    // ツモ損 drops the North share, 北家折半 splits it between the payers
+    #[test]
+    fn sanma_tsumo_follows_the_rule() {
+        let tsumo_son = RuleSet::preset("sanma").unwrap();
+        let bisection = RuleSet {
+            sanma_tsumo: SanmaTsumo::NorthBisection,
+            ..tsumo_son.clone()
+        };
+
+        // 2 han 40 fu
+        let ko = score(sanma_tsumo(Kaze::Nan), &tsumo_son);
+        assert_eq!(payments(&ko), (1300, 700, 2000));
+        assert_eq!(ko.point_deltas, Some([-1300, 2000, -700, 0]));
+        let ko = score(sanma_tsumo(Kaze::Nan), &bisection);
+        assert_eq!(payments(&ko), (1600, 1000, 2600));
+        assert_eq!(ko.point_deltas, Some([-1600, 2600, -1000, 0]));
+
+        // 1 han 40 fu
+        let oya = score(sanma_tsumo(Kaze::Ton), &tsumo_son);
+        assert_eq!(payments(&oya), (700, 0, 1400));
+        assert_eq!(oya.point_deltas, Some([1400, -700, -700, 0]));
+        let oya = score(sanma_tsumo(Kaze::Ton), &bisection);
+        assert_eq!(payments(&oya), (1000, 0, 2000));
+        assert_eq!(oya.point_deltas, Some([2000, -1000, -1000, 0]));
+    }
+
+    // 1000 per honba on ron, 500 from each of the two tsumo payers
+    #[test]
+    fn sanma_honba_follows_the_tsumo_value() {
+        let rules = RuleSet {
+            honba_value: 1000,
+            honba_tsumo_value: 500,
+            ..RuleSet::preset("sanma").unwrap()
+        };
+        assert_eq!(rules.validate(), Ok(()));
+
+        let tsumo = score(sanma_tsumo(Kaze::Nan).honba(2), &rules);
+        assert_eq!(payments(&tsumo), (1300, 700, 4000));
+        assert_eq!(tsumo.point_deltas, Some([-2300, 4000, -1700, 0]));
+
+        let ron = parse_hand("123p456p789s11z22z 2z")
+            .unwrap()
+            .seat(Kaze::Nan)
+            .ron(Kaze::Shaa)
+            .honba(2);
+        // yakuhai 1 han, 40 fu off a shanpon
+        assert_eq!(score(ron, &rules).total_payment, 1300 + 2000);
+    }
+
+    // 抜き北: each North set aside is a han, and a dora when North is
+    #[test]
+    fn sanma_kita_is_nuki_dora() {
+        let rules = RuleSet::preset("sanma").unwrap();
+        let west: Hai = "3z".parse().unwrap();
+        let result = score(sanma_tsumo(Kaze::Nan).kita(2).dora([west]), &rules);
+        let count = |yaku| result.yaku_list.iter().filter(|y| **y == yaku).count();
+        assert_eq!((count(Yaku::NukiDora), count(Yaku::Dora)), (2, 2));
+        assert_eq!(result.num_nukidora, 2);
+        // 6 han: haneman, 6000 from the dealer and 3000 from the other player
+        assert_eq!(payments(&result), (6000, 3000, 9000));
+    }
 }
@@ -1,28 +1,112 @@
-use crate::implements::types::yaku::Yaku;
-
-pub fn count_yakuman(yaku_list: &[Yaku]) -> u32 {
-    yaku_list
-        .iter()
-        .map(|yaku| match yaku {
-            // Double Yakuman
-            Yaku::SuuankouTanki => 2,
-            Yaku::KokushiMusouJusanmen => 2,
-            Yaku::JunseiChuurenPoutou => 2,
-            // Single Yakuman
-            Yaku::Tenhou => 1,
-            Yaku::Chiihou => 1,
-            Yaku::Renhou => 1,
-            Yaku::Daisangen => 1,
-            Yaku::Suuankou => 1,
-            Yaku::Daisuushi => 1,
-            Yaku::Shousuushi => 1,
-            Yaku::Tsuuiisou => 1,
-            Yaku::Chinroutou => 1,
-            Yaku::Ryuuiisou => 1,
-            Yaku::Suukantsu => 1,
-            Yaku::KokushiMusou => 1,
-            Yaku::ChuurenPoutou => 1,
-            _ => 0,
-        })
-        .sum()
+use crate::implements::types::{
+    rules::{RenhouValue, RuleSet},
+    yaku::Yaku,
+};
+
+// yakuman multiplier of the hand (0 for a regular hand)
+pub fn count_yakuman(yaku_list: &[Yaku], rules: &RuleSet) -> u32 {
+    let double = |enabled: bool| if enabled { 2 } else { 1 };
+    let values = yaku_list.iter().map(|yaku| match yaku {
+        // Double Yakuman (by rule)
+        Yaku::SuuankouTanki => double(rules.double_suuankou_tanki),
+        Yaku::KokushiMusouJusanmen => double(rules.double_kokushi_jusanmen),
+        Yaku::JunseiChuurenPoutou => double(rules.double_junsei_chuuren),
+        Yaku::Daisuushi => double(rules.double_daisuushi),
+        // Yakuman only by rule
+        Yaku::Renhou => (rules.renhou == RenhouValue::Yakuman) as u32,
+        Yaku::OpenRiichiDealIn => 1,
+        // Single Yakuman
+        Yaku::Tenhou => 1,
+        Yaku::Chiihou => 1,
+        Yaku::Daisangen => 1,
+        Yaku::Suuankou => 1,
+        Yaku::Shousuushi => 1,
+        Yaku::Tsuuiisou => 1,
+        Yaku::Chinroutou => 1,
+        Yaku::Ryuuiisou => 1,
+        Yaku::Suukantsu => 1,
+        Yaku::KokushiMusou => 1,
+        Yaku::ChuurenPoutou => 1,
+        _ => 0,
+    });
+
+    if rules.stack_yakuman {
+        values.sum()
+    } else {
+        values.max().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::implements::types::{rules::RuleSet, tiles::Kaze};
+    use crate::implements::{calculate_agari, parse_hand};
+
+    // non-dealer ron: 32000 per yakuman
+    fn ron_payment(text: &str, rules: &RuleSet) -> u32 {
+        let input = parse_hand(text)
+            .unwrap()
+            .seat(Kaze::Nan)
+            .ron(Kaze::Shaa)
+            .build()
+            .unwrap();
+        calculate_agari(&input, rules).unwrap().total_payment
+    }
+
+    // 複合役満: daisangen and tsuuiisou add up, or only one counts
+    #[test]
+    fn stack_yakuman_follows_the_rule() {
+        let hand = "666z777z11z22z 555z+ 2z";
+        let single = RuleSet {
+            stack_yakuman: false,
+            ..RuleSet::default()
+        };
+        assert_eq!(ron_payment(hand, &RuleSet::default()), 64000);
+        assert_eq!(ron_payment(hand, &single), 32000);
+    }
+
+    #[test]
+    fn double_suuankou_tanki_follows_the_rule() {
+        let hand = "111m333p555s777z1s 1s";
+        let single = RuleSet {
+            double_suuankou_tanki: false,
+            ..RuleSet::default()
+        };
+        assert_eq!(ron_payment(hand, &RuleSet::default()), 64000);
+        assert_eq!(ron_payment(hand, &single), 32000);
+    }
+
+    #[test]
+    fn double_kokushi_jusanmen_follows_the_rule() {
+        let hand = "19m19p19s1234567z 1m";
+        let single = RuleSet {
+            double_kokushi_jusanmen: false,
+            ..RuleSet::default()
+        };
+        assert_eq!(ron_payment(hand, &RuleSet::default()), 64000);
+        assert_eq!(ron_payment(hand, &single), 32000);
+    }
+
+    #[test]
+    fn double_junsei_chuuren_follows_the_rule() {
+        let hand = "1112345678999m 5m";
+        let single = RuleSet {
+            double_junsei_chuuren: false,
+            ..RuleSet::default()
+        };
+        assert_eq!(ron_payment(hand, &RuleSet::default()), 64000);
+        assert_eq!(ron_payment(hand, &single), 32000);
+    }
+
+    // off by default
+    #[test]
+    fn double_daisuushi_follows_the_rule() {
+        let hand = "333z444z5m 111z+ 222z+ 5m";
+        let double = RuleSet {
+            double_daisuushi: true,
+            ..RuleSet::default()
+        };
+        assert_eq!(ron_payment(hand, &RuleSet::default()), 32000);
+        assert_eq!(ron_payment(hand, &double), 64000);
+    }
 }
@@ -0,0 +1,425 @@
+use super::error::AgariError;
+use super::game::{AgariType, GameContext, PlayerContext};
+use super::hand::MentsuType;
+use super::input::{ClosedKanInput, OpenMeldInput, UserInput};
+use super::tiles::{Hai, Kaze};
+
+// Fluent construction of UserInput
+// hand() takes the concealed tiles without the winning tile
+#[derive(Debug, Clone, Default)]
+pub struct UserInputBuilder {
+    hand_tiles: Vec<Hai>,
+    winning_tile: Option<Hai>,
+    open_melds: Vec<OpenMeldInput>,
+    closed_kans: Vec<ClosedKanInput>,
+    last_meld: Option<MeldSlot>,
+    player_context: PlayerContext,
+    game_context: GameContext,
+    agari_type: AgariType,
+}
+
+// meld declared last, for meld_akadora()
+#[derive(Debug, Clone, Copy)]
+enum MeldSlot {
+    Open(usize),
+    Closed(usize),
+}
+
+impl UserInput {
+    pub fn builder() -> UserInputBuilder {
+        UserInputBuilder::default()
+    }
+}
+
+impl UserInputBuilder {
+    // Hand Composition
+    pub fn hand(mut self, tiles: impl IntoIterator<Item = Hai>) -> Self {
+        self.hand_tiles.extend(tiles);
+        self
+    }
+
+    pub fn win_on(mut self, tile: Hai) -> Self {
+        self.winning_tile = Some(tile);
+        self
+    }
+
+    pub fn tsumo(mut self) -> Self {
+        self.agari_type = AgariType::Tsumo;
+        self.game_context.discarder = None;
+        self
+    }
+
+    // 栄和 off the given seat's discard
+    pub fn ron(mut self, discarder: Kaze) -> Self {
+        self.agari_type = AgariType::Ron;
+        self.game_context.discarder = Some(discarder);
+        self
+    }
+
+    // Melds
+    // a red five given as the meld's tile counts as one 赤ドラ
+    // 吃: representative tile is the lowest of the sequence
+    pub fn chii(self, start: Hai) -> Self {
+        self.open_meld(MentsuType::Shuntsu, start, false)
+    }
+
+    // 碰
+    pub fn pon(self, tile: Hai) -> Self {
+        self.open_meld(MentsuType::Koutsu, tile, false)
+    }
+
+    // 大明槓
+    pub fn open_kan(self, tile: Hai) -> Self {
+        self.open_meld(MentsuType::Kantsu, tile, false)
+    }
+
+    // 加槓
+    pub fn added_kan(self, tile: Hai) -> Self {
+        self.open_meld(MentsuType::Kantsu, tile, true)
+    }
+
+    // 暗槓
+    pub fn closed_kan(mut self, tile: Hai) -> Self {
+        self.last_meld = Some(MeldSlot::Closed(self.closed_kans.len()));
+        self.closed_kans.push(ClosedKanInput {
+            tile,
+            num_akadora: 0,
+        });
+        self
+    }
+
+    // 赤ドラ among the tiles of the meld declared last
+    pub fn meld_akadora(mut self, count: u8) -> Self {
+        match self.last_meld {
+            Some(MeldSlot::Open(i)) => self.open_melds[i].num_akadora = count,
+            Some(MeldSlot::Closed(i)) => self.closed_kans[i].num_akadora = count,
+            None => {}
+        }
+        self
+    }
+
+    fn open_meld(mut self, mentsu_type: MentsuType, tile: Hai, is_added_kan: bool) -> Self {
+        self.last_meld = Some(MeldSlot::Open(self.open_melds.len()));
+        self.open_melds.push(OpenMeldInput {
+            mentsu_type,
+            representative_tile: tile,
+            is_added_kan,
+            num_akadora: 0,
+        });
+        self
+    }
+
+    // Player Context
+    pub fn seat(mut self, jikaze: Kaze) -> Self {
+        self.player_context.jikaze = jikaze;
+        self
+    }
+
+    pub fn riichi(mut self) -> Self {
+        self.player_context.is_riichi = true;
+        self
+    }
+
+    // also declares riichi
+    pub fn double_riichi(mut self) -> Self {
+        self.player_context.is_riichi = true;
+        self.player_context.is_daburu_riichi = true;
+        self
+    }
+
+    // also declares riichi
+    pub fn open_riichi(mut self) -> Self {
+        self.player_context.is_riichi = true;
+        self.player_context.is_open_riichi = true;
+        self
+    }
+
+    pub fn ippatsu(mut self) -> Self {
+        self.player_context.is_ippatsu = true;
+        self
+    }
+
+    // 抜き北: North tiles set aside in a three-player game
+    pub fn kita(mut self, count: u8) -> Self {
+        self.player_context.num_kita = count;
+        self
+    }
+
+    // Game Context
+    pub fn discarder_in_riichi(mut self) -> Self {
+        self.game_context.is_discarder_riichi = true;
+        self
+    }
+
+    pub fn round(mut self, bakaze: Kaze) -> Self {
+        self.game_context.bakaze = bakaze;
+        self
+    }
+
+    pub fn dealer(mut self, oya_kaze: Kaze) -> Self {
+        self.game_context.oya_kaze = oya_kaze;
+        self
+    }
+
+    pub fn honba(mut self, honba: u8) -> Self {
+        self.game_context.honba = honba;
+        self
+    }
+
+    pub fn kyoutaku(mut self, sticks: u8) -> Self {
+        self.game_context.kyoutaku = sticks;
+        self
+    }
+
+    pub fn dora(mut self, indicators: impl IntoIterator<Item = Hai>) -> Self {
+        self.game_context.dora_indicators.extend(indicators);
+        self
+    }
+
+    pub fn ura_dora(mut self, indicators: impl IntoIterator<Item = Hai>) -> Self {
+        self.game_context.uradora_indicators.extend(indicators);
+        self
+    }
+
+    // Special yaku flags
+    pub fn tenhou(mut self) -> Self {
+        self.game_context.is_tenhou = true;
+        self
+    }
+
+    pub fn chiihou(mut self) -> Self {
+        self.game_context.is_chiihou = true;
+        self
+    }
+
+    pub fn renhou(mut self) -> Self {
+        self.game_context.is_renhou = true;
+        self
+    }
+
+    pub fn haitei(mut self) -> Self {
+        self.game_context.is_haitei = true;
+        self
+    }
+
+    pub fn houtei(mut self) -> Self {
+        self.game_context.is_houtei = true;
+        self
+    }
+
+    pub fn rinshan(mut self) -> Self {
+        self.game_context.is_rinshan = true;
+        self
+    }
+
+    pub fn chankan(mut self) -> Self {
+        self.game_context.is_chankan = true;
+        self
+    }
+
+    // derive menzen/oya, place the winning tile and validate
+    pub fn build(self) -> Result<UserInput, AgariError> {
+        let winning_tile = self.winning_tile.ok_or(AgariError::MissingWinningTile)?;
+
+        let mut hand_tiles = self.hand_tiles;
+        if self.agari_type == AgariType::Tsumo {
+            hand_tiles.push(winning_tile);
+        }
+
+        let mut input = UserInput {
+            hand_tiles,
+            winning_tile,
+            open_melds: self.open_melds,
+            closed_kans: self.closed_kans,
+            player_context: self.player_context,
+            game_context: self.game_context,
+            agari_type: self.agari_type,
+        };
+        input.player_context.is_menzen = input.is_menzen();
+        input.player_context.is_oya = input.is_oya();
+
+        input.validate()?;
+        Ok(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::implements::types::{
+        error::AgariError,
+        game::AgariType,
+        hand::MentsuType,
+        input::{ClosedKanInput, OpenMeldInput, UserInput},
+        rules::RuleSet,
+        tiles::{Hai, Kaze},
+    };
+    use crate::implements::{calculate_agari, parse_hand};
+
+    fn tile(text: &str) -> Hai {
+        text.parse().unwrap()
+    }
+
+    fn tiles(text: &[&str]) -> Vec<Hai> {
+        text.iter().map(|t| tile(t)).collect()
+    }
+
+    fn meld(
+        mentsu_type: MentsuType,
+        tile_text: &str,
+        is_added_kan: bool,
+        reds: u8,
+    ) -> OpenMeldInput {
+        OpenMeldInput {
+            mentsu_type,
+            representative_tile: tile(tile_text),
+            is_added_kan,
+            num_akadora: reds,
+        }
+    }
+
+    // chii 234m, pon 777z, daiminkan 9999p, shouminkan 5505s, 1s tanki
+    #[test]
+    fn open_melds() {
+        let input = UserInput::builder()
+            .hand(tiles(&["1s"]))
+            .win_on(tile("1s"))
+            .ron(Kaze::Nan)
+            .chii(tile("2m"))
+            .pon(tile("7z"))
+            .open_kan(tile("9p"))
+            .added_kan(tile("5s"))
+            .meld_akadora(1)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            input.open_melds,
+            vec![
+                meld(MentsuType::Shuntsu, "2m", false, 0),
+                meld(MentsuType::Koutsu, "7z", false, 0),
+                meld(MentsuType::Kantsu, "9p", false, 0),
+                meld(MentsuType::Kantsu, "5s", true, 1),
+            ]
+        );
+        assert!(!input.player_context.is_menzen);
+        assert_eq!(input.hand_tiles, tiles(&["1s"]));
+        assert_eq!(input.agari_type, AgariType::Ron);
+        assert_eq!(input.game_context.discarder, Some(Kaze::Nan));
+
+        let result = calculate_agari(&input, &RuleSet::default()).unwrap();
+        assert_eq!(result.num_akadora, 1);
+    }
+
+    // a closed kan keeps the hand concealed, so riichi is allowed
+    #[test]
+    fn closed_kan() {
+        let input = UserInput::builder()
+            .hand(tiles(&[
+                "2m", "3m", "4m", "6p", "7p", "8p", "2s", "2s", "7s", "8s",
+            ]))
+            .win_on(tile("9s"))
+            .tsumo()
+            .riichi()
+            .closed_kan(tile("0p"))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            input.closed_kans,
+            vec![ClosedKanInput {
+                tile: tile("0p"),
+                num_akadora: 0,
+            }]
+        );
+        assert!(input.open_melds.is_empty());
+        assert!(input.player_context.is_menzen);
+        // Tsumo places the winning tile in the hand
+        assert_eq!(input.hand_tiles.len(), 11);
+        assert_eq!(input.hand_tiles.last(), Some(&tile("9s")));
+
+        let result = calculate_agari(&input, &RuleSet::default()).unwrap();
+        assert_eq!(result.num_akadora, 1);
+    }
+
+    // 親 follows the dealer seat, not the East label
+    #[test]
+    fn dealer_is_derived_from_the_dealer_seat() {
+        let build = |seat: Kaze, dealer: Kaze| {
+            UserInput::builder()
+                .hand(tiles(&[
+                    "1m", "2m", "3m", "4p", "5p", "6p", "7s", "8s", "9s", "1z", "1z", "2p", "3p",
+                ]))
+                .win_on(tile("4p"))
+                .tsumo()
+                .seat(seat)
+                .dealer(dealer)
+                .build()
+                .unwrap()
+        };
+        assert!(build(Kaze::Nan, Kaze::Nan).player_context.is_oya);
+        assert!(!build(Kaze::Ton, Kaze::Shaa).player_context.is_oya);
+        assert!(build(Kaze::Ton, Kaze::Ton).player_context.is_oya);
+    }
+
+    #[test]
+    fn build_rejects_invalid_input() {
+        let missing = UserInput::builder().hand(tiles(&["1m"])).build();
+        assert!(matches!(missing, Err(AgariError::MissingWinningTile)));
+
+        let open_riichi = parse_hand("123m456p789s1s 777z+ 1s")
+            .unwrap()
+            .ron(Kaze::Nan)
+            .riichi()
+            .build();
+        assert!(matches!(
+            open_riichi,
+            Err(AgariError::InvalidContext {
+                flag: "Riichi",
+                requires: "a closed hand",
+            })
+        ));
+
+        let five_copies = parse_hand("1111m456p789s11z22z 1m")
+            .unwrap()
+            .ron(Kaze::Nan)
+            .build();
+        assert!(matches!(
+            five_copies,
+            Err(AgariError::TooManyCopies { count: 5, .. })
+        ));
+
+        let own_discard = parse_hand("123m456p789s11z22z 2z")
+            .unwrap()
+            .ron(Kaze::Ton)
+            .build();
+        assert!(matches!(
+            own_discard,
+            Err(AgariError::InvalidContext {
+                flag: "Discarder",
+                ..
+            })
+        ));
+
+        // a pon takes three tiles out of the concealed part
+        let short = parse_hand("123m456p789s11z 222z+ 1z")
+            .unwrap()
+            .ron(Kaze::Nan)
+            .build();
+        assert!(matches!(
+            short,
+            Err(AgariError::WrongTileCount {
+                expected: 11,
+                found: 12,
+            })
+        ));
+
+        let five_melds = parse_hand("1z 111m+ 222p+ 333s+ 444s+ 555z+ 1z")
+            .unwrap()
+            .ron(Kaze::Nan)
+            .build();
+        assert!(matches!(
+            five_melds,
+            Err(AgariError::TooManyMelds { count: 5 })
+        ));
+    }
+}
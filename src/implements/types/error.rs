@@ -0,0 +1,197 @@
+use super::tiles::Hai;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+// reasons a hand cannot be scored
+pub enum AgariError {
+    // RuleSet that fails RuleSet::validate
+    InvalidRules(RuleError),
+
+    // Malformed input
+    // builder finished without a winning tile
+    MissingWinningTile,
+    // more than 4 copies of one tile
+    TooManyCopies {
+        tile: Hai,
+        count: u8,
+    },
+    // more than 4 declared melds
+    TooManyMelds {
+        count: usize,
+    },
+    // concealed tile count does not fit the declared melds
+    WrongTileCount {
+        expected: usize,
+        found: usize,
+    },
+    // chii must start on 1-7 of a suit
+    InvalidChiiStart {
+        tile: Hai,
+    },
+    // more red fives than the set (or a meld's fives) allow
+    TooManyAkadora {
+        tile: Hai,
+        count: u8,
+        max: u8,
+    },
+    // 2m-8m in a three-player game
+    NotInTileSet {
+        tile: Hai,
+    },
+    // flag set without its prerequisite
+    InvalidContext {
+        flag: &'static str,
+        requires: &'static str,
+    },
+    // flag disagrees with the melds or seats
+    ContextMismatch {
+        flag: &'static str,
+        expected: bool,
+    },
+
+    // Not a winning hand (no 4 melds + pair, chiitoitsu or kokushi)
+    IncompleteHand {
+        tiles: Vec<Hai>,
+    },
+
+    // Winning hand without yaku
+    NoYaku {
+        tiles: Vec<Hai>,
+    },
+
+    // Winning hand the table rules do not allow (kuitan, atozuke, ryanhan shibari)
+    RestrictedByRules {
+        rule: &'static str,
+        han: u8, // han from yaku, dora excluded
+        required: u8,
+    },
+}
+
+impl fmt::Display for AgariError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AgariError::InvalidRules(err) => write!(f, "Invalid rules: {}", err),
+            AgariError::MissingWinningTile => write!(f, "No winning tile was given"),
+            AgariError::TooManyCopies { tile, count } => {
+                write!(f, "{} appears {} times (at most 4 allowed)", tile, count)
+            }
+            AgariError::TooManyMelds { count } => {
+                write!(f, "{} melds declared (at most 4 allowed)", count)
+            }
+            AgariError::WrongTileCount { expected, found } => write!(
+                f,
+                "Expected {} concealed tiles for the declared melds, found {}",
+                expected, found
+            ),
+            AgariError::InvalidChiiStart { tile } => write!(
+                f,
+                "Invalid representative tile for Chii: {} (must be 1-7 of a suit)",
+                tile
+            ),
+            AgariError::TooManyAkadora { tile, count, max } => write!(
+                f,
+                "{} red fives counted for {} (at most {} allowed)",
+                count, tile, max
+            ),
+            AgariError::NotInTileSet { tile } => {
+                write!(f, "{} is not used in three-player games", tile)
+            }
+            AgariError::InvalidContext { flag, requires } => {
+                write!(f, "{} requires {}", flag, requires)
+            }
+            AgariError::ContextMismatch { flag, expected } => write!(
+                f,
+                "{} should be {} for the declared melds and seats",
+                flag, expected
+            ),
+            AgariError::IncompleteHand { tiles } => {
+                write!(f, "Hand is not complete: {}", join_tiles(tiles))
+            }
+            AgariError::NoYaku { tiles } => {
+                write!(f, "No valid Yaku found: {}", join_tiles(tiles))
+            }
+            AgariError::RestrictedByRules {
+                rule,
+                han,
+                required,
+            } => write!(
+                f,
+                "Not enough Yaku under the {} rule: {} han from Yaku, {} required",
+                rule, han, required
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AgariError {}
+
+fn join_tiles(tiles: &[Hai]) -> String {
+    tiles
+        .iter()
+        .map(|t| t.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+// text that is not a single MPSZ tile
+pub struct ParseHaiError {
+    pub text: String,
+}
+
+impl fmt::Display for ParseHaiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "'{}' is not a tile (expected e.g. 5m, 3p, 9s, 7z)",
+            self.text
+        )
+    }
+}
+
+impl std::error::Error for ParseHaiError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+// hand notation that cannot be read
+pub struct ParseHandError {
+    pub token: String,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for ParseHandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Cannot read '{}': {}", self.token, self.reason)
+    }
+}
+
+impl std::error::Error for ParseHandError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+// game record that cannot be imported
+pub struct ReplayError {
+    pub context: String, // where in the log, e.g. "AGARI #3"
+    pub reason: String,
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.context, self.reason)
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+// rule set or rule file that cannot be used
+pub struct RuleError {
+    pub context: String, // field name, or "rule file" for TOML errors
+    pub reason: String,
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.context, self.reason)
+    }
+}
+
+impl std::error::Error for RuleError {}
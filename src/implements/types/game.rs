@@ -1,19 +1,18 @@
 use super::tiles::{Hai, Kaze};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 // win type
 pub enum AgariType {
     Tsumo, // 自摸 (Self-draw)
-    Ron,   // 栄和 (Win off discard)
+    #[default]
+    Ron, // 栄和 (Win off discard)
 }
 
-impl Default for AgariType {
-    fn default() -> Self {
-        AgariType::Ron
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 // Context winning hand
 pub struct PlayerContext {
     pub jikaze: Kaze,           // 自風 (Seat Wind)
@@ -21,17 +20,24 @@ pub struct PlayerContext {
     pub is_riichi: bool,        // 立直 (Riichi)
     pub is_daburu_riichi: bool, // ダブル立直 (Double Riichi)
     pub is_ippatsu: bool,       // 一発 (Ippatsu)
+    pub is_open_riichi: bool,   // オープン立直 (Open Riichi)
     pub is_menzen: bool,        // 門前 (fully concealed)
+    pub num_kita: u8,           // 抜き北 (North tiles set aside, three-player only)
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 // Context current round
 pub struct GameContext {
     pub bakaze: Kaze,                 // 場風 (Prevalent Wind)
+    pub oya_kaze: Kaze,               // 親の自風 (Dealer's Seat Wind)
     pub honba: u8,                    // 本場 (Honba counter)
+    pub kyoutaku: u8,                 // 供託 (Riichi deposits on the table)
+    pub discarder: Option<Kaze>,      // 放銃者 (Discarder's seat, Ron only)
+    pub is_discarder_riichi: bool,    // 放銃者の立直 (Discarder was in riichi)
     pub dora_indicators: Vec<Hai>,    // ドラ表示牌 (Dora indicators)
     pub uradora_indicators: Vec<Hai>, // 裏ドラ表示牌 (Ura Dora indicators)
-    pub num_akadora: u8,              // 赤ドラ (Red Dora)
     // Special yaku flags
     pub is_tenhou: bool,  // 天和 (Blessing of Heaven)
     pub is_chiihou: bool, // 地和 (Blessing of Earth)
@@ -1,6 +1,8 @@
 use super::tiles::Hai;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum MentsuType {
     Shuntsu, // 順子 (Sequence)
     Koutsu,  // 刻子 (Triplet)
@@ -8,14 +10,80 @@ pub enum MentsuType {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "MentsuRepr", try_from = "MentsuRepr"))]
 pub struct Mentsu {
     // 面子 (Meld)
     pub mentsu_type: MentsuType,
-    pub is_minchou: bool, // 明張 (meld open?)
-    pub tiles: [Hai; 4],
+    pub is_minchou: bool, // 明張 (open for fu and yaku: called, or completed by ron)
+    pub is_called: bool,  // 副露 (called from another player's discard)
+    pub tiles: [Hai; 4],  // sequences and triplets repeat their last tile
+}
+
+impl Mentsu {
+    // tiles of the meld without the fourth-slot padding
+    pub fn meld_tiles(&self) -> &[Hai] {
+        match self.mentsu_type {
+            MentsuType::Kantsu => &self.tiles,
+            MentsuType::Shuntsu | MentsuType::Koutsu => &self.tiles[..3],
+        }
+    }
+}
+
+// serialized Mentsu: three tiles, four for a kan
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct MentsuRepr {
+    mentsu_type: MentsuType,
+    is_minchou: bool,
+    #[serde(default)]
+    is_called: bool,
+    tiles: Vec<Hai>,
+}
+
+#[cfg(feature = "serde")]
+impl From<Mentsu> for MentsuRepr {
+    fn from(mentsu: Mentsu) -> Self {
+        MentsuRepr {
+            mentsu_type: mentsu.mentsu_type,
+            is_minchou: mentsu.is_minchou,
+            is_called: mentsu.is_called,
+            tiles: mentsu.meld_tiles().to_vec(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<MentsuRepr> for Mentsu {
+    type Error = String;
+
+    fn try_from(repr: MentsuRepr) -> Result<Self, Self::Error> {
+        let expected = match repr.mentsu_type {
+            MentsuType::Kantsu => 4,
+            MentsuType::Shuntsu | MentsuType::Koutsu => 3,
+        };
+        if repr.tiles.len() != expected {
+            return Err(format!(
+                "{:?} takes {} tiles, got {}",
+                repr.mentsu_type,
+                expected,
+                repr.tiles.len()
+            ));
+        }
+        let mut tiles = [repr.tiles[expected - 1]; 4];
+        tiles[..expected].copy_from_slice(&repr.tiles);
+        Ok(Mentsu {
+            mentsu_type: repr.mentsu_type,
+            is_minchou: repr.is_minchou,
+            is_called: repr.is_called,
+            tiles,
+        })
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Machi {
     // 待ち (Wait)
     Ryanmen, // 両面 (Two-Sided)
@@ -30,6 +98,7 @@ pub enum Machi {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AgariHand {
     // 和了手 (Winning Hand)
     pub mentsu: [Mentsu; 4], // 面子 (4 melds)
@@ -49,6 +118,8 @@ pub enum HandOrganization {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "structure", rename_all = "snake_case"))]
 pub enum HandStructure {
     YonmentsuIchiatama(AgariHand),
 
@@ -63,13 +134,28 @@ pub enum HandStructure {
     KokushiMusou {
         tiles: [Hai; 13],
         atama: (Hai, Hai),
+        #[cfg_attr(feature = "serde", serde(rename = "agari_hai"))]
         _agari_hai: Hai,
+        #[cfg_attr(feature = "serde", serde(rename = "machi"))]
         _machi: Machi,
     },
 
     // 九蓮宝燈 (Nine Gates)
     ChuurenPoutou {
         hand: AgariHand,
+        #[cfg_attr(feature = "serde", serde(rename = "is_junsei"))]
         _is_junsei: bool, // 純正 (true 9-sided wait)
     },
 }
+
+impl HandStructure {
+    // wait of the winning tile
+    pub fn machi(&self) -> Machi {
+        match self {
+            HandStructure::YonmentsuIchiatama(hand) => hand.machi,
+            HandStructure::Chiitoitsu { machi, .. } => *machi,
+            HandStructure::KokushiMusou { _machi, .. } => *_machi,
+            HandStructure::ChuurenPoutou { hand, .. } => hand.machi,
+        }
+    }
+}
@@ -1,18 +1,93 @@
+use super::error::AgariError;
 use super::game::{AgariType, GameContext, PlayerContext};
 use super::hand::MentsuType;
-use super::tiles::Hai;
+use super::tiles::{
+    Hai, Jihai, Kaze, MAX_SHUNTSU_START, SUHAI_TILES_COUNT, Suhai, Suit, TILE_COUNT,
+    TILES_PER_SUHAI, index_to_tile, tile_to_index,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OpenMeldInput {
     // type of meld
     pub mentsu_type: MentsuType,
 
     // single tile that uniquely represents the meld
     pub representative_tile: Hai,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub is_added_kan: bool,
+
+    // red fives among the meld's tiles; a red representative tile counts as one
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub num_akadora: u8,
+}
+
+impl OpenMeldInput {
+    // all tiles of the meld, red fives marked
+    pub fn tiles(&self) -> Result<Vec<Hai>, AgariError> {
+        let tile = self.representative_tile.plain();
+        let mut tiles = match self.mentsu_type {
+            MentsuType::Shuntsu => {
+                let index = tile_to_index(&tile);
+                if index >= SUHAI_TILES_COUNT || index % TILES_PER_SUHAI >= MAX_SHUNTSU_START {
+                    return Err(AgariError::InvalidChiiStart { tile });
+                }
+                vec![tile, index_to_tile(index + 1), index_to_tile(index + 2)]
+            }
+            MentsuType::Koutsu => vec![tile; 3],
+            MentsuType::Kantsu => vec![tile; 4],
+        };
+        let reds = self
+            .num_akadora
+            .max(self.representative_tile.is_red() as u8);
+        mark_red_fives(&mut tiles, reds)?;
+        Ok(tiles)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+// 暗槓 (closed kan)
+pub struct ClosedKanInput {
+    pub tile: Hai,
+
+    // red fives among the four tiles; a red tile counts as one
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub num_akadora: u8,
+}
+
+impl ClosedKanInput {
+    // all four tiles, red fives marked
+    pub fn tiles(&self) -> Result<Vec<Hai>, AgariError> {
+        let mut tiles = vec![self.tile.plain(); 4];
+        mark_red_fives(&mut tiles, self.num_akadora.max(self.tile.is_red() as u8))?;
+        Ok(tiles)
+    }
+}
+
+// the first `count` fives turn red
+fn mark_red_fives(tiles: &mut [Hai], count: u8) -> Result<(), AgariError> {
+    let is_five = |tile: &Hai| matches!(tile, Hai::Suhai(Suhai { number: 5, .. }));
+    let fives = tiles.iter().filter(|tile| is_five(tile)).count() as u8;
+    if count > fives {
+        return Err(AgariError::TooManyAkadora {
+            tile: tiles[0],
+            count,
+            max: fives,
+        });
+    }
+    for tile in tiles
+        .iter_mut()
+        .filter(|tile| is_five(tile))
+        .take(count as usize)
+    {
+        *tile = tile.red();
+    }
+    Ok(())
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UserInput {
     // Hand Composition
     pub hand_tiles: Vec<Hai>,
@@ -20,12 +95,364 @@ pub struct UserInput {
     pub winning_tile: Hai,
 
     // list of all open melds
+    #[cfg_attr(feature = "serde", serde(default))]
     pub open_melds: Vec<OpenMeldInput>,
 
     // list of all closed kans
-    pub closed_kans: Vec<Hai>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub closed_kans: Vec<ClosedKanInput>,
 
+    #[cfg_attr(feature = "serde", serde(default))]
     pub player_context: PlayerContext,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub game_context: GameContext,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub agari_type: AgariType,
 }
+
+impl UserInput {
+    // 門前: no open melds (closed kans keep the hand concealed)
+    pub fn is_menzen(&self) -> bool {
+        self.open_melds.is_empty()
+    }
+
+    // 親: seat wind matches the dealer's seat
+    pub fn is_oya(&self) -> bool {
+        self.player_context.jikaze == self.game_context.oya_kaze
+    }
+
+    // reject contradictory flags and impossible tile counts
+    pub fn validate(&self) -> Result<(), AgariError> {
+        self.validate_tiles()?;
+        self.validate_shape()?;
+        self.validate_context()
+    }
+
+    // 赤ドラ: no more red fives of a suit than the set has, indicators included
+    pub fn validate_akadora(&self, red_fives: &[u8; 3]) -> Result<(), AgariError> {
+        let tiles = self.all_tiles()?;
+        let indicators = self
+            .game_context
+            .dora_indicators
+            .iter()
+            .chain(&self.game_context.uradora_indicators);
+        let reds: Vec<Hai> = tiles
+            .iter()
+            .chain(indicators)
+            .filter(|tile| tile.is_red())
+            .map(|tile| tile.plain())
+            .collect();
+
+        for (suit, &max) in [Suit::Manzu, Suit::Pinzu, Suit::Souzu]
+            .into_iter()
+            .zip(red_fives)
+        {
+            let five = Hai::Suhai(Suhai {
+                number: 5,
+                suit,
+                is_red: false,
+            });
+            let count = reds.iter().filter(|&&tile| tile == five).count() as u8;
+            if count > max {
+                return Err(AgariError::TooManyAkadora {
+                    tile: five,
+                    count,
+                    max,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    // 三人麻雀: no 2m-8m, chii or North seat; 抜き北 only exists there
+    pub fn validate_players(&self, sanma: bool) -> Result<(), AgariError> {
+        let player = &self.player_context;
+        let game = &self.game_context;
+        if !sanma {
+            if player.num_kita > 0 {
+                return Err(AgariError::InvalidContext {
+                    flag: "Kita",
+                    requires: "three-player rules",
+                });
+            }
+            return Ok(());
+        }
+
+        if self
+            .open_melds
+            .iter()
+            .any(|meld| meld.mentsu_type == MentsuType::Shuntsu)
+        {
+            return Err(AgariError::InvalidContext {
+                flag: "Chii",
+                requires: "four players",
+            });
+        }
+        if [Some(player.jikaze), Some(game.oya_kaze), game.discarder].contains(&Some(Kaze::Pei)) {
+            return Err(AgariError::InvalidContext {
+                flag: "North seat",
+                requires: "four players",
+            });
+        }
+
+        let tiles = self.all_tiles()?;
+        let indicators = game.dora_indicators.iter().chain(&game.uradora_indicators);
+        if let Some(tile) = tiles
+            .iter()
+            .chain(indicators)
+            .find(|tile| !tile.is_sanma_tile())
+        {
+            return Err(AgariError::NotInTileSet { tile: tile.plain() });
+        }
+        Ok(())
+    }
+
+    // 赤ドラ in the hand, melds included
+    pub fn num_akadora(&self) -> Result<u8, AgariError> {
+        let tiles = self.all_tiles()?;
+        Ok(tiles.iter().filter(|tile| tile.is_red()).count() as u8)
+    }
+
+    // 門前 and 親 as the engine reads them, from the melds and the dealer seat
+    pub fn with_derived_flags(mut self) -> UserInput {
+        self.player_context.is_menzen = self.is_menzen();
+        self.player_context.is_oya = self.is_oya();
+        self
+    }
+
+    // the same hand with plain fives only
+    pub fn without_red_fives(&self) -> UserInput {
+        let plain = |tiles: &[Hai]| tiles.iter().map(|tile| tile.plain()).collect();
+        let mut input = self.clone();
+        input.hand_tiles = plain(&self.hand_tiles);
+        input.winning_tile = self.winning_tile.plain();
+        for meld in &mut input.open_melds {
+            meld.representative_tile = meld.representative_tile.plain();
+            meld.num_akadora = 0;
+        }
+        for kan in &mut input.closed_kans {
+            kan.tile = kan.tile.plain();
+            kan.num_akadora = 0;
+        }
+        input.game_context.dora_indicators = plain(&self.game_context.dora_indicators);
+        input.game_context.uradora_indicators = plain(&self.game_context.uradora_indicators);
+        input
+    }
+
+    // tiles of the hand, melds included
+    fn all_tiles(&self) -> Result<Vec<Hai>, AgariError> {
+        let mut tiles = self.hand_tiles.clone();
+        if self.agari_type == AgariType::Ron {
+            tiles.push(self.winning_tile);
+        }
+        for meld in &self.open_melds {
+            tiles.extend(meld.tiles()?);
+        }
+        for kan in &self.closed_kans {
+            tiles.extend(kan.tiles()?);
+        }
+        Ok(tiles)
+    }
+
+    fn tile_counts(&self) -> Result<[u8; TILE_COUNT], AgariError> {
+        let mut counts = [0u8; TILE_COUNT];
+        // saturating: an oversized hand still fails the copy check below
+        for tile in self.all_tiles()? {
+            let count = &mut counts[tile_to_index(&tile)];
+            *count = count.saturating_add(1);
+        }
+        // 抜き北 set aside
+        let pei = tile_to_index(&Hai::Jihai(Jihai::Kaze(Kaze::Pei)));
+        counts[pei] = counts[pei].saturating_add(self.player_context.num_kita);
+        Ok(counts)
+    }
+
+    fn validate_tiles(&self) -> Result<(), AgariError> {
+        // tiles in hand
+        let mut counts = self.tile_counts()?;
+
+        // tiles on the table
+        let indicators = self
+            .game_context
+            .dora_indicators
+            .iter()
+            .chain(&self.game_context.uradora_indicators);
+        for tile in indicators {
+            let count = &mut counts[tile_to_index(tile)];
+            *count = count.saturating_add(1);
+        }
+
+        if let Some((index, &count)) = counts.iter().enumerate().find(|&(_, &c)| c > 4) {
+            return Err(AgariError::TooManyCopies {
+                tile: index_to_tile(index),
+                count,
+            });
+        }
+
+        Ok(())
+    }
+
+    // 14 tiles in all: each meld takes three of them out of the hand
+    fn validate_shape(&self) -> Result<(), AgariError> {
+        let meld_count = self.open_melds.len() + self.closed_kans.len();
+        if meld_count > 4 {
+            return Err(AgariError::TooManyMelds { count: meld_count });
+        }
+        let expected = 14 - 3 * meld_count;
+        let found = self.hand_tiles.len() + (self.agari_type == AgariType::Ron) as usize;
+        if found != expected {
+            return Err(AgariError::WrongTileCount { expected, found });
+        }
+        Ok(())
+    }
+
+    fn validate_context(&self) -> Result<(), AgariError> {
+        let player = &self.player_context;
+        let game = &self.game_context;
+        let is_tsumo = self.agari_type == AgariType::Tsumo;
+        let is_ron = self.agari_type == AgariType::Ron;
+        let is_menzen = self.is_menzen();
+        let is_oya = self.is_oya();
+
+        // 門前 and 親 are derived; a flag set against the melds or seats is an error
+        let derived = [
+            (player.is_menzen, is_menzen, "is_menzen"),
+            (player.is_oya, is_oya, "is_oya"),
+        ];
+        for (supplied, expected, flag) in derived {
+            if supplied && !expected {
+                return Err(AgariError::ContextMismatch { flag, expected });
+            }
+        }
+
+        let requirements = [
+            (player.is_riichi, is_menzen, "Riichi", "a closed hand"),
+            (
+                player.is_daburu_riichi,
+                player.is_riichi,
+                "Double Riichi",
+                "Riichi",
+            ),
+            (player.is_ippatsu, player.is_riichi, "Ippatsu", "Riichi"),
+            (
+                player.is_open_riichi,
+                player.is_riichi,
+                "Open Riichi",
+                "Riichi",
+            ),
+            (
+                game.is_tenhou,
+                is_tsumo && is_oya && is_menzen,
+                "Tenhou",
+                "a closed Tsumo by the dealer",
+            ),
+            (
+                game.is_chiihou,
+                is_tsumo && !is_oya && is_menzen,
+                "Chiihou",
+                "a closed Tsumo by a non-dealer",
+            ),
+            (
+                game.is_renhou,
+                is_ron && !is_oya && is_menzen,
+                "Renhou",
+                "a closed Ron by a non-dealer",
+            ),
+            (game.is_haitei, is_tsumo, "Haitei", "Tsumo"),
+            (game.is_rinshan, is_tsumo, "Rinshan", "Tsumo"),
+            (game.is_houtei, is_ron, "Houtei", "Ron"),
+            (game.is_chankan, is_ron, "Chankan", "Ron"),
+            (game.discarder.is_some(), is_ron, "Discarder", "Ron"),
+            (game.is_discarder_riichi, is_ron, "Discarder Riichi", "Ron"),
+            (
+                game.discarder == Some(player.jikaze),
+                false,
+                "Discarder",
+                "a seat other than the winner",
+            ),
+        ];
+
+        for (is_set, is_allowed, flag, requires) in requirements {
+            if is_set && !is_allowed {
+                return Err(AgariError::InvalidContext { flag, requires });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::implements::notation::parse_hand;
+    use crate::implements::types::{
+        builder::UserInputBuilder,
+        error::AgariError,
+        tiles::{Hai, Kaze},
+    };
+
+    // 256+ copies must not wrap the u8 count back under 5
+    #[test]
+    fn oversized_hand_is_rejected() {
+        let one_man = "1m".parse().unwrap();
+        let mut input = parse_hand("123m456p789s11z22z 2z")
+            .unwrap()
+            .ron(Kaze::Nan)
+            .build()
+            .unwrap();
+        input.hand_tiles = vec![one_man; 255];
+        assert!(matches!(
+            input.validate(),
+            Err(AgariError::TooManyCopies { count: 255, .. })
+        ));
+    }
+
+    // 三人麻雀: no chii, no North seat, no 2m-8m anywhere
+    #[test]
+    fn sanma_rejects_four_player_hands() {
+        let sanma = |builder: UserInputBuilder| builder.build().unwrap().validate_players(true);
+        let hand = || parse_hand("123p456p789s11z22z 2z").unwrap().tsumo();
+
+        assert_eq!(sanma(hand().seat(Kaze::Nan)), Ok(()));
+        assert_eq!(
+            sanma(parse_hand("456p789s11z22z 123p+ 2z").unwrap().tsumo()),
+            Err(AgariError::InvalidContext {
+                flag: "Chii",
+                requires: "four players",
+            })
+        );
+        let north_seat = Err(AgariError::InvalidContext {
+            flag: "North seat",
+            requires: "four players",
+        });
+        assert_eq!(sanma(hand().seat(Kaze::Pei)), north_seat);
+        assert_eq!(
+            sanma(
+                parse_hand("123p456p789s11z22z 2z")
+                    .unwrap()
+                    .seat(Kaze::Nan)
+                    .ron(Kaze::Pei)
+            ),
+            north_seat
+        );
+
+        let three_man: Hai = "3m".parse().unwrap();
+        let not_in_set = Err(AgariError::NotInTileSet { tile: three_man });
+        assert_eq!(
+            sanma(parse_hand("345m456p789s11z22z 2z").unwrap().tsumo()),
+            not_in_set
+        );
+        assert_eq!(sanma(hand().dora([three_man])), not_in_set);
+
+        // 抜き北 needs three-player rules
+        let kita = hand().seat(Kaze::Nan).kita(1).build().unwrap();
+        assert_eq!(kita.validate_players(true), Ok(()));
+        assert_eq!(
+            kita.validate_players(false),
+            Err(AgariError::InvalidContext {
+                flag: "Kita",
+                requires: "three-player rules",
+            })
+        );
+    }
+}
@@ -1,6 +1,9 @@
+pub mod builder;
+pub mod error;
 pub mod game;
 pub mod hand;
 pub mod input;
+pub mod rules;
 pub mod scoring;
 pub mod tiles;
 pub mod yaku;
@@ -0,0 +1,219 @@
+use super::error::RuleError;
+
+// ルール (table rules that differ between clubs and platforms)
+// The default matches the engine's long-standing behaviour.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct RuleSet {
+    pub kiriage_mangan: bool, // 切り上げ満貫: 4 han 30 fu and 3 han 60 fu score as mangan
+    pub kazoe_yakuman: bool,  // 数え役満: 13+ han is yakuman, otherwise capped at sanbaiman
+    pub stack_yakuman: bool,  // 複合役満: yakuman add up, otherwise only the largest counts
+
+    // 和了の制限 (what counts as a win)
+    pub kuitan: bool,                // 喰いタン: open tanyao counts as a yaku
+    pub atozuke: bool, // 後付け: when off, every wait of the tenpai hand must have a yaku
+    pub ryanhan_shibari: Option<u8>, // 二飜縛り: from this many honba, 2 han without dora are needed
+
+    // ダブル役満 (hands worth two yakuman)
+    pub double_suuankou_tanki: bool,   // 四暗刻単騎
+    pub double_kokushi_jusanmen: bool, // 国士無双十三面待ち
+    pub double_junsei_chuuren: bool,   // 純正九蓮宝燈
+    pub double_daisuushi: bool,        // 大四喜
+
+    // ローカル役 (optional yaku and fu)
+    pub renhou: RenhouValue,
+    pub open_riichi: bool, // オープン立直: one han on top of riichi; otherwise scored as riichi
+    pub open_riichi_deal_in_yakuman: bool, // a player not in riichi dealing into it pays yakuman
+    pub double_wind_pair_fu: u8, // 連風牌: pair of the seat and round wind, 2 or 4 fu
+    pub chiitoitsu_four_of_a_kind: bool, // 七対子 with four identical tiles as two pairs
+    pub chiitoitsu_fu: u8, // 七対子 flat fu, 25 or 50
+    pub rinshan_tsumo_fu: bool, // 嶺上開花 earns the 2 tsumo fu
+
+    // 三人麻雀 (three players: 2m-8m removed, North set aside as 抜きドラ)
+    pub sanma: bool,
+    pub sanma_tsumo: SanmaTsumo,
+
+    // 道具 (equipment and table money)
+    pub red_fives: [u8; 3],     // 赤ドラ: red fives in the set (man, pin, sou)
+    pub honba_value: u32,       // 積み棒: points per honba on ron
+    pub honba_tsumo_value: u32, // 積み棒: points per honba from each payer on tsumo
+}
+
+// 三人麻雀 tsumo: how the missing North seat's share is settled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum SanmaTsumo {
+    TsumoSon,       // ツモ損: nobody pays it, a tsumo is worth less than a ron
+    NorthBisection, // 北家折半: the two payers split it
+}
+
+// 人和 value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum RenhouValue {
+    Off,     // not a yaku
+    Mangan,  // worth at least mangan, other yaku do not add to it
+    Baiman,  // worth at least baiman, other yaku do not add to it
+    FiveHan, // 5 han yaku, adds to the other yaku
+    Yakuman, // 役満
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        RuleSet {
+            kiriage_mangan: false,
+            kazoe_yakuman: true,
+            stack_yakuman: true,
+            kuitan: true,
+            atozuke: true,
+            ryanhan_shibari: None,
+            double_suuankou_tanki: true,
+            double_kokushi_jusanmen: true,
+            double_junsei_chuuren: true,
+            double_daisuushi: false,
+            renhou: RenhouValue::Yakuman,
+            open_riichi: false,
+            open_riichi_deal_in_yakuman: false,
+            double_wind_pair_fu: 4,
+            chiitoitsu_four_of_a_kind: false,
+            chiitoitsu_fu: 25,
+            rinshan_tsumo_fu: true,
+            sanma: false,
+            sanma_tsumo: SanmaTsumo::TsumoSon,
+            red_fives: [1, 2, 1],
+            honba_value: 300,
+            honba_tsumo_value: 100,
+        }
+    }
+}
+
+impl RuleSet {
+    // names accepted by preset()
+    pub const PRESETS: [&'static str; 6] = ["standard", "tenhou", "majsoul", "wrc", "ema", "sanma"];
+
+    // bundled rule sets; "standard" is the default
+    pub fn preset(name: &str) -> Option<RuleSet> {
+        let standard = RuleSet::default();
+        let single_yakuman = RuleSet {
+            double_suuankou_tanki: false,
+            double_kokushi_jusanmen: false,
+            double_junsei_chuuren: false,
+            double_daisuushi: false,
+            renhou: RenhouValue::Off,
+            ..standard.clone()
+        };
+        let preset = match name.to_ascii_lowercase().as_str() {
+            "standard" => standard,
+            // 天鳳 (one red five per suit, no double yakuman or renhou)
+            "tenhou" => RuleSet {
+                red_fives: [1, 1, 1],
+                ..single_yakuman
+            },
+            // 雀魂 (every double yakuman, no renhou)
+            "majsoul" => RuleSet {
+                double_daisuushi: true,
+                renhou: RenhouValue::Off,
+                red_fives: [1, 1, 1],
+                ..standard
+            },
+            // World Riichi Championship
+            "wrc" => RuleSet {
+                kiriage_mangan: true,
+                kazoe_yakuman: false,
+                double_wind_pair_fu: 2,
+                red_fives: [0, 0, 0],
+                ..single_yakuman
+            },
+            // European Mahjong Association
+            "ema" => RuleSet {
+                kazoe_yakuman: false,
+                renhou: RenhouValue::Mangan,
+                red_fives: [0, 0, 0],
+                ..single_yakuman
+            },
+            // 天鳳 三人麻雀 (red 5p and 5s, tsumo-son)
+            "sanma" => RuleSet {
+                sanma: true,
+                red_fives: [0, 1, 1],
+                ..single_yakuman
+            },
+            _ => return None,
+        };
+        Some(preset)
+    }
+
+    // reject values no table plays with
+    pub fn validate(&self) -> Result<(), RuleError> {
+        let error = |context: &str, reason: &str| {
+            Err(RuleError {
+                context: context.to_string(),
+                reason: reason.to_string(),
+            })
+        };
+        if !matches!(self.double_wind_pair_fu, 2 | 4) {
+            return error("double_wind_pair_fu", "must be 2 or 4");
+        }
+        if !matches!(self.chiitoitsu_fu, 25 | 50) {
+            return error("chiitoitsu_fu", "must be 25 or 50");
+        }
+        if self.red_fives.iter().any(|&count| count > 4) {
+            return error("red_fives", "a suit has only four fives");
+        }
+        if self.sanma && self.red_fives[0] > 0 {
+            return error("red_fives", "three-player sets have no 5m");
+        }
+        if !self.honba_value.is_multiple_of(100) {
+            return error("honba_value", "must be a multiple of 100");
+        }
+        if !self.honba_tsumo_value.is_multiple_of(100) {
+            return error("honba_tsumo_value", "must be a multiple of 100");
+        }
+        if self.open_riichi_deal_in_yakuman && !self.open_riichi {
+            return error("open_riichi_deal_in_yakuman", "requires open_riichi");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "toml")]
+impl RuleSet {
+    // rule file with any RuleSet field; missing fields keep the default
+    pub fn from_toml(text: &str) -> Result<RuleSet, RuleError> {
+        let rules: RuleSet = toml::from_str(text).map_err(|err| RuleError {
+            context: "rule file".to_string(),
+            reason: err.to_string(),
+        })?;
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    // every field written out, as a starting point for a rule file
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("RuleSet serializes to TOML")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RuleSet;
+
+    #[test]
+    fn presets_are_valid() {
+        for name in RuleSet::PRESETS {
+            let rules = RuleSet::preset(name).unwrap();
+            assert_eq!(rules.validate(), Ok(()), "{}", name);
+        }
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn presets_round_trip_through_toml() {
+        for name in RuleSet::PRESETS {
+            let rules = RuleSet::preset(name).unwrap();
+            assert_eq!(RuleSet::from_toml(&rules.to_toml()), Ok(rules), "{}", name);
+        }
+    }
+}
@@ -1,19 +1,55 @@
 use super::game::AgariType;
+use super::hand::{HandStructure, Machi, MentsuType};
+use super::tiles::{Hai, Kaze};
 use super::yaku::Yaku;
 
 // fu calculation
-pub const FU_CHIITOITSU: u8 = 25;
 pub const FU_PINFU_TSUMO: u8 = 20;
 pub const FU_PINFU_RON: u8 = 30;
+pub const FU_OPEN_PINFU: u8 = 10;
 pub const FU_BASE: u8 = 20;
 pub const FU_MENZEN_RON: u8 = 10;
 pub const FU_TSUMO: u8 = 2;
 pub const FU_PAIR_SINGLE_WAIT: u8 = 2;
-pub const FU_PAIR_WIND: u32 = 2;
-pub const FU_PAIR_DRAGON: u32 = 2;
+pub const FU_PAIR_WIND: u8 = 2;
+pub const FU_PAIR_DRAGON: u8 = 2;
 pub const FU_ROUND_UP: u8 = 10;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+// source of a fu contribution
+pub enum FuKind {
+    // Special cases
+    Chiitoitsu, // 七対子 (flat, 25 by default)
+    PinfuTsumo, // 平和自摸 (flat 20)
+    OpenPinfu,  // 喰い平和形 (open all-sequence Ron, raised to 30)
+
+    // Standard fu
+    Base,      // 副底
+    MenzenRon, // 門前加符
+    Tsumo,     // 自摸符
+    Mentsu {
+        mentsu_type: MentsuType,
+        is_open: bool,
+        is_yaochuu: bool, // terminal or honor
+    },
+    Pair(Hai),   // 雀頭 (yakuhai pair)
+    Wait(Machi), // 待ち (kanchan, penchan, tanki)
+    RoundUp,     // 切り上げ
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+// one line of the fu breakdown
+pub struct FuEntry {
+    pub kind: FuKind,
+    pub fu: u8,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 // point limits
 pub enum HandLimit {
     Mangan,    // 満貫
@@ -24,17 +60,31 @@ pub enum HandLimit {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 // result for a winning hand
 pub struct AgariResult {
-    pub han: u8,              // 飜 (Han count)
-    pub fu: u8,               // 符 (Fu count)
-    pub yaku_list: Vec<Yaku>, // all yaku and dora achieved
-    pub num_akadora: u8,      // 赤ドラ (Red Dora)
+    pub hand_structure: HandStructure, // interpretation that was scored
+    pub machi: Machi,                  // 待ち (wait type)
+    pub han: u8,                       // 飜 (Han count)
+    pub fu: u8,                        // 符 (Fu count)
+    pub fu_breakdown: Vec<FuEntry>,    // itemized fu, sums to fu
+    pub yaku_list: Vec<Yaku>,          // all yaku and dora achieved
+    pub num_akadora: u8,               // 赤ドラ (Red Dora)
+    pub num_nukidora: u8,              // 抜きドラ (North set aside, three-player only)
     pub limit_name: Option<HandLimit>,
     pub oya_payment: u32,
     pub ko_payment: u32,
     pub total_payment: u32,
     pub honba: u8,
+    pub kyoutaku: u8,
     pub agari_type: AgariType,
     pub is_oya: bool,
+    pub point_deltas: Option<[i32; 4]>, // 点数移動 (East, South, West, North); None for a Ron with no discarder
+}
+
+impl AgariResult {
+    // points gained (+) or paid (-) by a seat, if the payers are known
+    pub fn point_delta(&self, seat: Kaze) -> Option<i32> {
+        self.point_deltas.map(|deltas| deltas[seat as usize])
+    }
 }
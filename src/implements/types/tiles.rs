@@ -1,3 +1,5 @@
+use super::error::ParseHaiError;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Suit {
     // 数牌 (Number)
@@ -14,19 +16,19 @@ pub const MAX_SHUNTSU_START: usize = 7;
 pub const CHIITOITSU_PAIR_COUNT: usize = 7;
 pub const STANDARD_HAND_SIZE: usize = 14;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Kaze {
     // 風牌 (Wind)
-    Ton,  // 東 (East)
-    Nan,  // 南 (South)
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "east"))]
+    Ton, // 東 (East)
+    #[cfg_attr(feature = "serde", serde(rename = "south"))]
+    Nan, // 南 (South)
+    #[cfg_attr(feature = "serde", serde(rename = "west"))]
     Shaa, // 西 (West)
-    Pei,  // 北 (North)
-}
-
-impl Default for Kaze {
-    fn default() -> Self {
-        Kaze::Ton
-    }
+    #[cfg_attr(feature = "serde", serde(rename = "north"))]
+    Pei, // 北 (North)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -48,6 +50,7 @@ pub enum Jihai {
 pub struct Suhai {
     pub number: u8,
     pub suit: Suit,
+    pub is_red: bool, // 赤ドラ (red five)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -83,6 +86,115 @@ impl Hai {
     pub fn is_yaochuu(&self) -> bool {
         self.is_terminal() || self.is_jihai()
     }
+
+    // used in 三人麻雀 (2m-8m are removed)
+    pub fn is_sanma_tile(&self) -> bool {
+        !matches!(
+            self,
+            Hai::Suhai(Suhai {
+                suit: Suit::Manzu,
+                number: 2..=8,
+                ..
+            })
+        )
+    }
+
+    // red five (赤ドラ)
+    pub fn is_red(&self) -> bool {
+        matches!(
+            self,
+            Hai::Suhai(Suhai {
+                number: 5,
+                is_red: true,
+                ..
+            })
+        )
+    }
+
+    // red copy of a five; other tiles are returned unchanged
+    pub fn red(self) -> Hai {
+        match self {
+            Hai::Suhai(suhai) if suhai.number == 5 => Hai::Suhai(Suhai {
+                is_red: true,
+                ..suhai
+            }),
+            _ => self,
+        }
+    }
+
+    // same tile without the red marking
+    pub fn plain(self) -> Hai {
+        match self {
+            Hai::Suhai(suhai) => Hai::Suhai(Suhai {
+                is_red: false,
+                ..suhai
+            }),
+            Hai::Jihai(_) => self,
+        }
+    }
+}
+
+// MPSZ notation (5m, 0p for a red five, 7z)
+impl std::fmt::Display for Hai {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Hai::Suhai(Suhai { number, suit, .. }) => {
+                let suit_char = match suit {
+                    Suit::Manzu => 'm',
+                    Suit::Pinzu => 'p',
+                    Suit::Souzu => 's',
+                };
+                let number = if self.is_red() { 0 } else { *number };
+                write!(f, "{}{}", number, suit_char)
+            }
+            Hai::Jihai(_) => write!(f, "{}z", tile_to_index(self) - SUHAI_TILES_COUNT + 1),
+        }
+    }
+}
+
+impl std::str::FromStr for Hai {
+    type Err = ParseHaiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseHaiError {
+            text: s.to_string(),
+        };
+        let mut chars = s.chars();
+        let (Some(digit), Some(suit), None) = (chars.next(), chars.next(), chars.next()) else {
+            return Err(err());
+        };
+        let number = digit.to_digit(10).ok_or_else(err)? as usize;
+
+        // 0 is a red five
+        let is_red = number == 0 && suit != 'z';
+        let number = if is_red { 5 } else { number };
+
+        let index = match (suit, number) {
+            ('m', 1..=9) => number - 1,
+            ('p', 1..=9) => number - 1 + TILES_PER_SUHAI,
+            ('s', 1..=9) => number - 1 + TILES_PER_SUHAI * 2,
+            ('z', 1..=7) => number - 1 + SUHAI_TILES_COUNT,
+            _ => return Err(err()),
+        };
+        let tile = index_to_tile(index);
+        Ok(if is_red { tile.red() } else { tile })
+    }
+}
+
+// tiles as MPSZ strings
+#[cfg(feature = "serde")]
+impl serde::Serialize for Hai {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Hai {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
 }
 
 pub fn tile_to_index(tile: &Hai) -> usize {
@@ -90,14 +202,17 @@ pub fn tile_to_index(tile: &Hai) -> usize {
         Hai::Suhai(Suhai {
             number: n,
             suit: Suit::Manzu,
+            ..
         }) => (*n - 1) as usize, // 0-8
         Hai::Suhai(Suhai {
             number: n,
             suit: Suit::Pinzu,
+            ..
         }) => (*n - 1) as usize + TILES_PER_SUHAI, // 9-17
         Hai::Suhai(Suhai {
             number: n,
             suit: Suit::Souzu,
+            ..
         }) => (*n - 1) as usize + TILES_PER_SUHAI * 2, // 18-26
         Hai::Jihai(Jihai::Kaze(Kaze::Ton)) => SUHAI_TILES_COUNT,
         Hai::Jihai(Jihai::Kaze(Kaze::Nan)) => SUHAI_TILES_COUNT + 1,
@@ -114,14 +229,17 @@ pub fn index_to_tile(index: usize) -> Hai {
         0..=8 => Hai::Suhai(Suhai {
             number: (index + 1) as u8,
             suit: Suit::Manzu,
+            is_red: false,
         }),
         9..=17 => Hai::Suhai(Suhai {
             number: ((index - TILES_PER_SUHAI) + 1) as u8,
             suit: Suit::Pinzu,
+            is_red: false,
         }),
         18..=26 => Hai::Suhai(Suhai {
             number: ((index - TILES_PER_SUHAI * 2) + 1) as u8,
             suit: Suit::Souzu,
+            is_red: false,
         }),
         27 => Hai::Jihai(Jihai::Kaze(Kaze::Ton)),
         28 => Hai::Jihai(Jihai::Kaze(Kaze::Nan)),
@@ -133,3 +251,13 @@ pub fn index_to_tile(index: usize) -> Hai {
         _ => panic!("Invalid tile index: {}", index),
     }
 }
+
+pub fn counts_to_tiles(counts: &[u8; TILE_COUNT]) -> Vec<Hai> {
+    let mut tiles = Vec::new();
+    for (index, &count) in counts.iter().enumerate() {
+        for _ in 0..count {
+            tiles.push(index_to_tile(index));
+        }
+    }
+    tiles
+}
@@ -1,4 +1,6 @@
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Yaku {
     // 1 Han
     Riichi,        // 立直 (Riichi)
@@ -60,6 +62,13 @@ pub enum Yaku {
     Dora,    // ドラ (Dora)
     UraDora, // 裏ドラ (Ura Dora)
     AkaDora, // 赤ドラ (Red Five Dora)
+
+    // Optional (by rule)
+    OpenRiichi,       // オープン立直 (Open Riichi), 1 han over riichi
+    OpenRiichiDealIn, // オープン立直放銃 (dealt in by a player not in riichi), yakuman
+
+    // Three-player only
+    NukiDora, // 抜きドラ (North set aside)
 }
 
 impl std::fmt::Display for Yaku {
@@ -114,7 +123,94 @@ impl std::fmt::Display for Yaku {
             Yaku::Dora => "Dora",
             Yaku::UraDora => "Ura Dora",
             Yaku::AkaDora => "Aka Dora",
+            Yaku::OpenRiichi => "Open Riichi",
+            Yaku::OpenRiichiDealIn => "Open Riichi Deal-in",
+            Yaku::NukiDora => "Nuki Dora",
         };
         write!(f, "{}", name)
     }
 }
+
+impl Yaku {
+    // every variant, in declaration order
+    pub const ALL: [Yaku; 52] = [
+        Yaku::Riichi,
+        Yaku::Ippatsu,
+        Yaku::MenzenTsumo,
+        Yaku::Pinfu,
+        Yaku::Iipeikou,
+        Yaku::HaiteiRaoyue,
+        Yaku::HouteiRaoyui,
+        Yaku::RinshanKaihou,
+        Yaku::Chankan,
+        Yaku::Tanyao,
+        Yaku::YakuhaiJikaze,
+        Yaku::YakuhaiBakaze,
+        Yaku::YakuhaiHaku,
+        Yaku::YakuhaiHatsu,
+        Yaku::YakuhaiChun,
+        Yaku::DaburuRiichi,
+        Yaku::Chiitoitsu,
+        Yaku::SanshokuDoujun,
+        Yaku::Ittsu,
+        Yaku::Chanta,
+        Yaku::Toitoi,
+        Yaku::Sanankou,
+        Yaku::SanshokuDoukou,
+        Yaku::Sankantsu,
+        Yaku::Shousangen,
+        Yaku::Honroutou,
+        Yaku::Ryanpeikou,
+        Yaku::Junchan,
+        Yaku::Honitsu,
+        Yaku::Chinitsu,
+        Yaku::Tenhou,
+        Yaku::Chiihou,
+        Yaku::Renhou,
+        Yaku::Daisangen,
+        Yaku::Suuankou,
+        Yaku::Daisuushi,
+        Yaku::Shousuushi,
+        Yaku::Tsuuiisou,
+        Yaku::Chinroutou,
+        Yaku::Ryuuiisou,
+        Yaku::Suukantsu,
+        Yaku::KokushiMusou,
+        Yaku::ChuurenPoutou,
+        Yaku::SuuankouTanki,
+        Yaku::KokushiMusouJusanmen,
+        Yaku::JunseiChuurenPoutou,
+        Yaku::Dora,
+        Yaku::UraDora,
+        Yaku::AkaDora,
+        Yaku::OpenRiichi,
+        Yaku::OpenRiichiDealIn,
+        Yaku::NukiDora,
+    ];
+
+    // 門前限定 (only scored with a closed hand)
+    pub fn is_menzen_only(&self) -> bool {
+        matches!(
+            self,
+            Yaku::Riichi
+                | Yaku::Ippatsu
+                | Yaku::MenzenTsumo
+                | Yaku::Pinfu
+                | Yaku::Iipeikou
+                | Yaku::DaburuRiichi
+                | Yaku::OpenRiichi
+                | Yaku::OpenRiichiDealIn
+                | Yaku::Chiitoitsu
+                | Yaku::Ryanpeikou
+                | Yaku::Tenhou
+                | Yaku::Chiihou
+                | Yaku::Renhou
+                | Yaku::Suuankou
+                | Yaku::SuuankouTanki
+                | Yaku::KokushiMusou
+                | Yaku::KokushiMusouJusanmen
+                | Yaku::ChuurenPoutou
+                | Yaku::JunseiChuurenPoutou
+        )
+    }
+}
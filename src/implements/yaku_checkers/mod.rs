@@ -1,6 +1,9 @@
 use crate::implements::types::{
+    error::AgariError,
     game::{AgariType, GameContext, PlayerContext},
     hand::{HandOrganization, HandStructure},
+    rules::RuleSet,
+    tiles::{Hai, Jihai, Kaze, counts_to_tiles},
     yaku::Yaku,
 };
 
@@ -17,6 +20,7 @@ pub struct YakuResult {
     pub hand_structure: HandStructure,
     pub yaku_list: Vec<Yaku>,
     pub num_akadora: u8,
+    pub num_nukidora: u8,
 }
 
 pub fn check_all_yaku(
@@ -24,29 +28,28 @@ pub fn check_all_yaku(
     player: &PlayerContext,
     game: &GameContext,
     agari_type: AgariType,
-) -> Result<YakuResult, &'static str> {
+    num_akadora: u8,
+    rules: &RuleSet,
+) -> Result<YakuResult, AgariError> {
     // game-state Yakuman
-    let mut yakuman_list = check_game_state_yakuman(player, game);
+    let mut yakuman_list = check_game_state_yakuman(player, game, agari_type, rules);
 
     // hand-based Yakuman
     let (hand_structure, hand_yakuman) =
-        match resolve_hand_structure(organization, player, game, agari_type) {
-            Ok((structure, yakuman)) => (structure, yakuman),
-            Err(e) => return Err(e),
-        };
+        resolve_hand_structure(organization, player, game, agari_type, rules)?;
 
     yakuman_list.extend(hand_yakuman);
 
     // regular yaku
     let mut regular_yaku: Vec<Yaku> = match &hand_structure {
         HandStructure::YonmentsuIchiatama(agari_hand) => {
-            find_standard_yaku(agari_hand, player, game, agari_type)
+            find_standard_yaku(agari_hand, player, game, agari_type, rules)
         }
         HandStructure::Chiitoitsu {
             pairs,
             agari_hai,
             machi,
-        } => find_chiitoitsu_yaku(pairs, agari_hai, machi, player, game, agari_type),
+        } => find_chiitoitsu_yaku(pairs, agari_hai, machi, player, game, agari_type, rules),
         _ => vec![],
     };
 
@@ -56,38 +59,56 @@ pub fn check_all_yaku(
         regular_yaku.extend(final_yakuman);
     }
 
-    // Dora
-    let has_yaku = !regular_yaku.is_empty() || player.is_riichi || player.is_daburu_riichi;
-
-    let mut num_akadora_to_add = 0;
+    let all_tiles = get_all_tiles_from_structure(&hand_structure);
+
+    // Yaku check
+    if regular_yaku.is_empty() {
+        // open tanyao would have been the yaku
+        if let HandStructure::YonmentsuIchiatama(agari_hand) = &hand_structure
+            && !rules.kuitan
+            && !player.is_menzen
+            && standard::tanyao::check_tanyao(agari_hand)
+        {
+            return Err(AgariError::RestrictedByRules {
+                rule: "kuitan",
+                han: 0,
+                required: 1,
+            });
+        }
+        return Err(AgariError::NoYaku { tiles: all_tiles });
+    }
 
-    if has_yaku {
-        let all_tiles = get_all_tiles_from_structure(&hand_structure);
+    // Dora (抜き北 count when North is dora)
+    let mut dora_tiles = all_tiles.clone();
+    dora_tiles.extend(vec![
+        Hai::Jihai(Jihai::Kaze(Kaze::Pei));
+        player.num_kita as usize
+    ]);
+    let dora_count = count_dora(&dora_tiles, &game.dora_indicators, rules.sanma);
+    for _ in 0..dora_count {
+        regular_yaku.push(Yaku::Dora);
+    }
 
-        let dora_count = count_dora(&all_tiles, &game.dora_indicators);
-        for _ in 0..dora_count {
-            regular_yaku.push(Yaku::Dora);
+    if (player.is_riichi || player.is_daburu_riichi) && !game.uradora_indicators.is_empty() {
+        let uradora_count = count_dora(&dora_tiles, &game.uradora_indicators, rules.sanma);
+        for _ in 0..uradora_count {
+            regular_yaku.push(Yaku::UraDora);
         }
+    }
 
-        if (player.is_riichi || player.is_daburu_riichi) && !game.uradora_indicators.is_empty() {
-            let uradora_count = count_dora(&all_tiles, &game.uradora_indicators);
-            for _ in 0..uradora_count {
-                regular_yaku.push(Yaku::UraDora);
-            }
-        }
+    for _ in 0..num_akadora {
+        regular_yaku.push(Yaku::AkaDora);
+    }
 
-        if game.num_akadora > 0 {
-            num_akadora_to_add = game.num_akadora;
-            for _ in 0..game.num_akadora {
-                regular_yaku.push(Yaku::AkaDora);
-            }
-        }
+    for _ in 0..player.num_kita {
+        regular_yaku.push(Yaku::NukiDora);
     }
 
     Ok(YakuResult {
         hand_structure,
         yaku_list: regular_yaku,
-        num_akadora: num_akadora_to_add,
+        num_akadora,
+        num_nukidora: player.num_kita,
     })
 }
 
@@ -97,7 +118,8 @@ fn resolve_hand_structure(
     player: &PlayerContext,
     game: &GameContext,
     agari_type: AgariType,
-) -> Result<(HandStructure, Vec<Yaku>), &'static str> {
+    rules: &RuleSet,
+) -> Result<(HandStructure, Vec<Yaku>), AgariError> {
     match org {
         HandOrganization::YonmentsuIchiatama(agari_hand) => {
             let (yakuman_list, chuuren_flag) =
@@ -120,12 +142,58 @@ fn resolve_hand_structure(
                 Ok((kokushi_structure, vec![kokushi_yaku]))
             }
             // Chiitoitsu
-            else if let Some(chiitoitsu_structure) = check_chiitoitsu(&counts, agari_hai) {
+            else if let Some(chiitoitsu_structure) =
+                check_chiitoitsu(&counts, agari_hai, rules.chiitoitsu_four_of_a_kind)
+            {
                 let yakuman = check_chiitoitsu_yakuman(&chiitoitsu_structure);
                 Ok((chiitoitsu_structure, yakuman))
             } else {
-                Err("No Yaku Found")
+                Err(AgariError::IncompleteHand {
+                    tiles: counts_to_tiles(&counts),
+                })
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::implements::types::{
+        error::AgariError, input::UserInput, rules::RuleSet, tiles::Kaze, yaku::Yaku,
+    };
+    use crate::implements::{calculate_agari, parse_hand};
+
+    fn ron(text: &str) -> UserInput {
+        parse_hand(text)
+            .unwrap()
+            .seat(Kaze::Nan)
+            .ron(Kaze::Shaa)
+            .build()
+            .unwrap()
+    }
+
+    // 喰いタン: open tanyao is a yaku only under the rule
+    #[test]
+    fn open_tanyao_follows_kuitan() {
+        let rules = RuleSet {
+            kuitan: false,
+            ..RuleSet::default()
+        };
+        let open = ron("234m67p55s 678s+ 345s+ 5p");
+        assert_eq!(
+            calculate_agari(&open, &rules).unwrap_err(),
+            AgariError::RestrictedByRules {
+                rule: "kuitan",
+                han: 0,
+                required: 1,
+            }
+        );
+        let result = calculate_agari(&open, &RuleSet::default()).unwrap();
+        assert_eq!(result.yaku_list, [Yaku::Tanyao]);
+
+        // closed tanyao is unaffected
+        let closed = ron("234m67p55s678s345s 5p");
+        let result = calculate_agari(&closed, &rules).unwrap();
+        assert!(result.yaku_list.contains(&Yaku::Tanyao));
+    }
+}
@@ -4,7 +4,7 @@ use crate::implements::types::{
 };
 use std::collections::{HashMap, HashSet};
 
-pub fn check_ittsu<'a>(shuntsu: &[&'a Mentsu]) -> bool {
+pub fn check_ittsu(shuntsu: &[&Mentsu]) -> bool {
     if shuntsu.len() < 3 {
         return false;
     }
@@ -12,7 +12,10 @@ pub fn check_ittsu<'a>(shuntsu: &[&'a Mentsu]) -> bool {
     let mut suits: HashMap<Suit, HashSet<u8>> = HashMap::new();
 
     for m in shuntsu {
-        if let Hai::Suhai(Suhai { number: n, suit: s }) = m.tiles[0] {
+        if let Hai::Suhai(Suhai {
+            number: n, suit: s, ..
+        }) = m.tiles[0]
+        {
             suits.entry(s).or_default().insert(n);
         }
     }
@@ -21,6 +21,7 @@ use super::utils::*;
 use crate::implements::types::{
     game::{AgariType, GameContext, PlayerContext},
     hand::{AgariHand, Machi},
+    rules::{RenhouValue, RuleSet},
     tiles::Hai,
     yaku::Yaku,
 };
@@ -30,20 +31,14 @@ pub fn find_standard_yaku(
     player: &PlayerContext,
     game: &GameContext,
     agari_type: AgariType,
+    rules: &RuleSet,
 ) -> Vec<Yaku> {
     let mut yaku_list = Vec::new();
 
     let context = YakuCheckContext::new(hand);
 
     // context-based Yaku
-    if player.is_daburu_riichi {
-        yaku_list.push(Yaku::DaburuRiichi);
-    } else if player.is_riichi {
-        yaku_list.push(Yaku::Riichi);
-    }
-    if player.is_ippatsu {
-        yaku_list.push(Yaku::Ippatsu);
-    }
+    yaku_list.extend(find_declared_yaku(player, game, rules));
     if player.is_menzen && agari_type == AgariType::Tsumo {
         yaku_list.push(Yaku::MenzenTsumo);
     }
@@ -68,8 +63,8 @@ pub fn find_standard_yaku(
         yaku_list.push(Yaku::Pinfu);
     }
 
-    // Tanyao
-    if check_tanyao(hand) {
+    // Tanyao (open only with kuitan)
+    if check_tanyao(hand) && (player.is_menzen || rules.kuitan) {
         yaku_list.push(Yaku::Tanyao);
     }
 
@@ -151,20 +146,14 @@ pub fn find_chiitoitsu_yaku(
     player: &PlayerContext,
     game: &GameContext,
     agari_type: AgariType,
+    rules: &RuleSet,
 ) -> Vec<Yaku> {
     let mut yaku_list = Vec::new();
 
     yaku_list.push(Yaku::Chiitoitsu);
 
-    // Riichi/DaburuRiichi/Ippatsu
-    if player.is_daburu_riichi {
-        yaku_list.push(Yaku::DaburuRiichi);
-    } else if player.is_riichi {
-        yaku_list.push(Yaku::Riichi);
-    }
-    if player.is_ippatsu {
-        yaku_list.push(Yaku::Ippatsu);
-    }
+    // Riichi/DaburuRiichi/OpenRiichi/Ippatsu/Renhou
+    yaku_list.extend(find_declared_yaku(player, game, rules));
     // MenzenTsumo
     if agari_type == AgariType::Tsumo {
         yaku_list.push(Yaku::MenzenTsumo);
@@ -202,3 +191,107 @@ pub fn find_chiitoitsu_yaku(
 
     yaku_list
 }
+
+// riichi variants, ippatsu and a renhou below yakuman
+fn find_declared_yaku(player: &PlayerContext, game: &GameContext, rules: &RuleSet) -> Vec<Yaku> {
+    let mut yaku_list = Vec::new();
+    if player.is_daburu_riichi {
+        yaku_list.push(Yaku::DaburuRiichi);
+    } else if player.is_riichi {
+        yaku_list.push(Yaku::Riichi);
+    }
+    // the open bonus goes on top of riichi or double riichi
+    if player.is_open_riichi && rules.open_riichi {
+        yaku_list.push(Yaku::OpenRiichi);
+    }
+    if player.is_ippatsu {
+        yaku_list.push(Yaku::Ippatsu);
+    }
+    if game.is_renhou && !matches!(rules.renhou, RenhouValue::Off | RenhouValue::Yakuman) {
+        yaku_list.push(Yaku::Renhou);
+    }
+    yaku_list
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::implements::types::{
+        builder::UserInputBuilder,
+        rules::{RenhouValue, RuleSet},
+        scoring::HandLimit,
+        tiles::Kaze,
+        yaku::Yaku,
+    };
+    use crate::implements::{calculate_agari, parse_hand};
+
+    // closed pinfu, South seat, ron off West
+    fn pinfu_ron() -> UserInputBuilder {
+        parse_hand("234m567p345s99s78m 6m")
+            .unwrap()
+            .seat(Kaze::Nan)
+            .ron(Kaze::Shaa)
+    }
+
+    fn han_and_yaku(builder: UserInputBuilder, rules: &RuleSet) -> (u8, Vec<Yaku>) {
+        let result = calculate_agari(&builder.build().unwrap(), rules).unwrap();
+        (result.han, result.yaku_list)
+    }
+
+    // オープン立直: one han over riichi, and over double riichi
+    #[test]
+    fn open_riichi_adds_a_han_to_either_riichi() {
+        let rules = RuleSet {
+            open_riichi: true,
+            ..RuleSet::default()
+        };
+        assert_eq!(
+            han_and_yaku(pinfu_ron().riichi(), &rules),
+            (2, vec![Yaku::Riichi, Yaku::Pinfu])
+        );
+        assert_eq!(
+            han_and_yaku(pinfu_ron().open_riichi(), &rules),
+            (3, vec![Yaku::Riichi, Yaku::OpenRiichi, Yaku::Pinfu])
+        );
+        assert_eq!(
+            han_and_yaku(pinfu_ron().open_riichi().double_riichi(), &rules),
+            (4, vec![Yaku::DaburuRiichi, Yaku::OpenRiichi, Yaku::Pinfu])
+        );
+
+        // without the rule it is plain riichi
+        assert_eq!(
+            han_and_yaku(pinfu_ron().open_riichi(), &RuleSet::default()),
+            (2, vec![Yaku::Riichi, Yaku::Pinfu])
+        );
+    }
+
+    // 人和 on a 1 han hand under each value
+    #[test]
+    fn renhou_value_follows_the_rule() {
+        let score = |renhou| {
+            let rules = RuleSet {
+                renhou,
+                ..RuleSet::default()
+            };
+            let result = calculate_agari(&pinfu_ron().renhou().build().unwrap(), &rules).unwrap();
+            (result.han, result.limit_name, result.total_payment)
+        };
+        assert_eq!(score(RenhouValue::Off), (1, None, 1000));
+        assert_eq!(
+            score(RenhouValue::Mangan),
+            (5, Some(HandLimit::Mangan), 8000)
+        );
+        assert_eq!(
+            score(RenhouValue::Baiman),
+            (8, Some(HandLimit::Baiman), 16000)
+        );
+        assert_eq!(
+            score(RenhouValue::FiveHan),
+            (6, Some(HandLimit::Haneman), 12000)
+        );
+        // the yakuman's han still lists pinfu on top
+        assert_eq!(
+            score(RenhouValue::Yakuman),
+            (14, Some(HandLimit::Yakuman), 32000)
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use crate::implements::types::hand::Mentsu;
 use std::collections::HashMap;
 
-pub fn check_peikou<'a>(shuntsu: &[&'a Mentsu]) -> (bool, bool) {
+pub fn check_peikou(shuntsu: &[&Mentsu]) -> (bool, bool) {
     if shuntsu.len() < 2 {
         return (false, false);
     }
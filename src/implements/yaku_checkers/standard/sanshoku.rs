@@ -15,7 +15,10 @@ pub fn check_sanshoku_generic(mentsu_list: &[&Mentsu]) -> bool {
     // man, pin, sou check
     let mut starters: HashMap<u8, (bool, bool, bool)> = HashMap::new();
     for m in mentsu_list {
-        if let Hai::Suhai(Suhai { number: n, suit: s }) = m.tiles[0] {
+        if let Hai::Suhai(Suhai {
+            number: n, suit: s, ..
+        }) = m.tiles[0]
+        {
             let entry = starters.entry(n).or_insert((false, false, false));
             match s {
                 Suit::Manzu => entry.0 = true,
@@ -28,7 +31,7 @@ pub fn check_sanshoku_generic(mentsu_list: &[&Mentsu]) -> bool {
     starters.values().any(|&(m, p, s)| m && p && s)
 }
 
-pub fn check_sanshoku_doujun<'a>(shuntsu: &[&'a Mentsu]) -> bool {
+pub fn check_sanshoku_doujun(shuntsu: &[&Mentsu]) -> bool {
     check_sanshoku_generic(shuntsu)
 }
 
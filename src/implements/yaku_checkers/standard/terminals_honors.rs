@@ -11,10 +11,10 @@ pub fn check_shousangen(hand: &AgariHand) -> bool {
     let mut dragon_atama = false;
 
     for m in &hand.mentsu {
-        if is_koutsu_or_kantsu(m) {
-            if let Hai::Jihai(Jihai::Sangen(_)) = m.tiles[0] {
-                dragon_koutsu += 1;
-            }
+        if is_koutsu_or_kantsu(m)
+            && let Hai::Jihai(Jihai::Sangen(_)) = m.tiles[0]
+        {
+            dragon_koutsu += 1;
         }
     }
 
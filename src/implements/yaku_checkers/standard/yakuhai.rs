@@ -37,9 +37,7 @@ pub fn check_yakuhai(hand: &AgariHand, player: &PlayerContext, game: &GameContex
     }
 
     let jikaze_hai = Hai::Jihai(Jihai::Kaze(player.jikaze));
-    if koutsu_tiles.contains(&jikaze_hai) && jikaze_hai != bakaze_hai {
-        yaku.push(Yaku::YakuhaiJikaze);
-    } else if koutsu_tiles.contains(&jikaze_hai) && jikaze_hai == bakaze_hai {
+    if koutsu_tiles.contains(&jikaze_hai) {
         yaku.push(Yaku::YakuhaiJikaze);
     }
 
@@ -7,16 +7,23 @@ use crate::implements::types::{
     },
 };
 
-pub fn check_chiitoitsu(counts: &[u8; TILE_COUNT], agari_hai: Hai) -> Option<HandStructure> {
+// four identical tiles count as two pairs when allowed
+pub fn check_chiitoitsu(
+    counts: &[u8; TILE_COUNT],
+    agari_hai: Hai,
+    allow_four_of_a_kind: bool,
+) -> Option<HandStructure> {
     let mut pair_count = 0;
     let mut pairs = Vec::new();
 
     for (idx, &count) in counts.iter().enumerate() {
         if count > 0 {
-            if count == 2 {
-                pair_count += 1;
+            if count == 2 || (count == 4 && allow_four_of_a_kind) {
                 let tile = index_to_tile(idx);
-                pairs.push((tile, tile));
+                for _ in 0..count / 2 {
+                    pair_count += 1;
+                    pairs.push((tile, tile));
+                }
             } else {
                 return None;
             }
@@ -34,10 +41,10 @@ pub fn check_chiitoitsu(counts: &[u8; TILE_COUNT], agari_hai: Hai) -> Option<Han
     }
 }
 
-pub fn count_dora(all_tiles: &[Hai], indicators: &[Hai]) -> u8 {
+pub fn count_dora(all_tiles: &[Hai], indicators: &[Hai], sanma: bool) -> u8 {
     let mut count = 0;
     for indicator in indicators {
-        let dora_tile = get_dora_tile(indicator);
+        let dora_tile = get_dora_tile(indicator, sanma);
         for tile in all_tiles {
             if *tile == dora_tile {
                 count += 1;
@@ -47,18 +54,32 @@ pub fn count_dora(all_tiles: &[Hai], indicators: &[Hai]) -> u8 {
     count
 }
 
-pub fn get_dora_tile(indicator: &Hai) -> Hai {
+// 三人麻雀 has no 2m-8m, so a 1m indicator points at 9m
+pub fn get_dora_tile(indicator: &Hai, sanma: bool) -> Hai {
     match indicator {
-        Hai::Suhai(Suhai { number: n, suit: s }) => {
+        Hai::Suhai(Suhai {
+            number: 1,
+            suit: Suit::Manzu,
+            ..
+        }) if sanma => Hai::Suhai(Suhai {
+            number: 9,
+            suit: Suit::Manzu,
+            is_red: false,
+        }),
+        Hai::Suhai(Suhai {
+            number: n, suit: s, ..
+        }) => {
             if *n == 9 {
                 Hai::Suhai(Suhai {
                     number: 1,
                     suit: *s,
+                    is_red: false,
                 })
             } else {
                 Hai::Suhai(Suhai {
                     number: n + 1,
                     suit: *s,
+                    is_red: false,
                 })
             }
         }
@@ -145,10 +166,8 @@ pub fn count_concealed_koutsu(hand: &AgariHand, agari_type: AgariType) -> u8 {
         }
 
         if m.mentsu_type == MentsuType::Koutsu {
-            if agari_type == AgariType::Ron {
-                if m.tiles[0] == hand.agari_hai {
-                    continue;
-                }
+            if agari_type == AgariType::Ron && m.tiles[0] == hand.agari_hai {
+                continue;
             }
             count += 1;
         } else if m.mentsu_type == MentsuType::Kantsu {
@@ -167,6 +186,7 @@ pub fn is_green_tile(tile: &Hai) -> bool {
         Hai::Suhai(Suhai {
             number: n,
             suit: Suit::Souzu,
+            ..
         }) => *n == 2 || *n == 3 || *n == 4 || *n == 6 || *n == 8,
         Hai::Jihai(Jihai::Sangen(Sangenpai::Hatsu)) => true,
         _ => false,
@@ -198,3 +218,49 @@ impl<'a> YakuCheckContext<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{check_chiitoitsu, get_dora_tile};
+    use crate::implements::types::{
+        hand::HandStructure,
+        tiles::{Hai, TILE_COUNT, tile_to_index},
+    };
+
+    fn counts(text: &[&str]) -> [u8; TILE_COUNT] {
+        let mut counts = [0u8; TILE_COUNT];
+        for tile in text {
+            counts[tile_to_index(&tile.parse::<Hai>().unwrap())] += 1;
+        }
+        counts
+    }
+
+    // 1111m as two of the seven pairs
+    #[test]
+    fn chiitoitsu_four_of_a_kind_follows_the_rule() {
+        let hand = counts(&[
+            "1m", "1m", "1m", "1m", "2m", "2m", "3m", "3m", "4p", "4p", "5p", "5p", "6s", "6s",
+        ]);
+        let agari_hai: Hai = "6s".parse().unwrap();
+
+        assert!(check_chiitoitsu(&hand, agari_hai, false).is_none());
+        let Some(HandStructure::Chiitoitsu { pairs, .. }) =
+            check_chiitoitsu(&hand, agari_hai, true)
+        else {
+            panic!("not read as chiitoitsu");
+        };
+        let one_man: Hai = "1m".parse().unwrap();
+        assert_eq!(pairs[..2], [(one_man, one_man), (one_man, one_man)]);
+    }
+
+    // 三人麻雀 skips 2m-8m, so 1m indicates 9m and 9m wraps to 1m
+    #[test]
+    fn sanma_dora_indicator_skips_the_removed_manzu() {
+        let tile = |text: &str| text.parse::<Hai>().unwrap();
+        assert_eq!(get_dora_tile(&tile("1m"), true), tile("9m"));
+        assert_eq!(get_dora_tile(&tile("9m"), true), tile("1m"));
+        assert_eq!(get_dora_tile(&tile("1m"), false), tile("2m"));
+        assert_eq!(get_dora_tile(&tile("1p"), true), tile("2p"));
+        assert_eq!(get_dora_tile(&tile("4z"), true), tile("1z"));
+    }
+}
@@ -21,10 +21,12 @@ pub fn check_chuuren(hand: &AgariHand) -> Option<bool> {
     // count check
     let mut counts = [0u8; TILES_PER_SUHAI];
     for tile in &all_tiles {
-        if let Hai::Suhai(Suhai { number: n, suit: s }) = tile {
-            if *s == suit {
-                counts[(n - 1) as usize] += 1;
-            }
+        if let Hai::Suhai(Suhai {
+            number: n, suit: s, ..
+        }) = tile
+            && *s == suit
+        {
+            counts[(n - 1) as usize] += 1;
         }
     }
 
@@ -54,10 +56,13 @@ pub fn check_chuuren(hand: &AgariHand) -> Option<bool> {
     }
 
     // junsei check
-    if let Hai::Suhai(Suhai { number: n, suit: s }) = hand.agari_hai {
-        if s == suit && n as usize == extra_tile_num {
-            return Some(true);
-        }
+    if let Hai::Suhai(Suhai {
+        number: n, suit: s, ..
+    }) = hand.agari_hai
+        && s == suit
+        && n as usize == extra_tile_num
+    {
+        return Some(true);
     }
     // not junsei
     Some(false)
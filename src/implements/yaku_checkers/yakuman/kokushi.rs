@@ -52,11 +52,9 @@ pub fn check_kokushi(counts: &[u8; 34], agari_hai: Hai) -> Option<(HandStructure
     let mut final_machi = Machi::KokushiIchimen;
 
     // 13-sided wait
-    if atama.0 == agari_hai {
-        if tiles.len() == 13 {
-            yaku = Yaku::KokushiMusouJusanmen;
-            final_machi = Machi::KokushiJusanmen;
-        }
+    if atama.0 == agari_hai && tiles.len() == 13 {
+        yaku = Yaku::KokushiMusouJusanmen;
+        final_machi = Machi::KokushiJusanmen;
     }
 
     Some((
@@ -8,11 +8,17 @@ use super::utils::*;
 use crate::implements::types::{
     game::{AgariType, GameContext, PlayerContext},
     hand::{AgariHand, HandStructure, Machi},
+    rules::{RenhouValue, RuleSet},
     tiles::{Hai, Jihai},
     yaku::Yaku,
 };
 
-pub fn check_game_state_yakuman(_player: &PlayerContext, game: &GameContext) -> Vec<Yaku> {
+pub fn check_game_state_yakuman(
+    player: &PlayerContext,
+    game: &GameContext,
+    agari_type: AgariType,
+    rules: &RuleSet,
+) -> Vec<Yaku> {
     let mut yaku = Vec::new();
     if game.is_tenhou {
         yaku.push(Yaku::Tenhou);
@@ -20,9 +26,17 @@ pub fn check_game_state_yakuman(_player: &PlayerContext, game: &GameContext) ->
     if game.is_chiihou {
         yaku.push(Yaku::Chiihou);
     }
-    if game.is_renhou {
+    if game.is_renhou && rules.renhou == RenhouValue::Yakuman {
         yaku.push(Yaku::Renhou);
     }
+    if rules.open_riichi
+        && rules.open_riichi_deal_in_yakuman
+        && player.is_open_riichi
+        && agari_type == AgariType::Ron
+        && !game.is_discarder_riichi
+    {
+        yaku.push(Yaku::OpenRiichiDealIn);
+    }
     yaku
 }
 
@@ -83,10 +97,10 @@ pub fn check_standard_yakuman(
     // Daisangen
     let mut dragon_koutsu = 0;
     for mentsu in &hand.mentsu {
-        if is_koutsu_or_kantsu(mentsu) {
-            if let Hai::Jihai(Jihai::Sangen(_)) = mentsu.tiles[0] {
-                dragon_koutsu += 1;
-            }
+        if is_koutsu_or_kantsu(mentsu)
+            && let Hai::Jihai(Jihai::Sangen(_)) = mentsu.tiles[0]
+        {
+            dragon_koutsu += 1;
         }
     }
     if dragon_koutsu == 3 {
@@ -97,10 +111,10 @@ pub fn check_standard_yakuman(
     let mut wind_koutsu = 0;
     let mut wind_atama = false;
     for mentsu in &hand.mentsu {
-        if is_koutsu_or_kantsu(mentsu) {
-            if let Hai::Jihai(Jihai::Kaze(_)) = mentsu.tiles[0] {
-                wind_koutsu += 1;
-            }
+        if is_koutsu_or_kantsu(mentsu)
+            && let Hai::Jihai(Jihai::Kaze(_)) = mentsu.tiles[0]
+        {
+            wind_koutsu += 1;
         }
     }
     if let Hai::Jihai(Jihai::Kaze(_)) = hand.atama.0 {
@@ -157,3 +171,52 @@ pub fn post_process_yakuman(mut yakuman: Vec<Yaku>) -> Vec<Yaku> {
 
     yakuman
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::implements::types::{
+        builder::UserInputBuilder, rules::RuleSet, scoring::HandLimit, tiles::Kaze, yaku::Yaku,
+    };
+    use crate::implements::{calculate_agari, parse_hand};
+
+    // open riichi pinfu, South seat
+    fn open_riichi() -> UserInputBuilder {
+        parse_hand("234m567p345s99s78m 6m")
+            .unwrap()
+            .seat(Kaze::Nan)
+            .open_riichi()
+    }
+
+    // オープン立直放銃: only a discarder not in riichi pays yakuman
+    #[test]
+    fn open_riichi_deal_in_needs_a_discarder_out_of_riichi() {
+        let rules = RuleSet {
+            open_riichi: true,
+            open_riichi_deal_in_yakuman: true,
+            ..RuleSet::default()
+        };
+        let score = |builder: UserInputBuilder, rules: &RuleSet| {
+            let result = calculate_agari(&builder.build().unwrap(), rules).unwrap();
+            let deal_in = result.yaku_list.contains(&Yaku::OpenRiichiDealIn);
+            (deal_in, result.limit_name, result.total_payment)
+        };
+
+        let ron = open_riichi().ron(Kaze::Shaa);
+        assert_eq!(
+            score(ron.clone(), &rules),
+            (true, Some(HandLimit::Yakuman), 32000)
+        );
+        // riichi, open riichi and pinfu: 3 han 30 fu
+        assert_eq!(
+            score(ron.clone().discarder_in_riichi(), &rules),
+            (false, None, 3900)
+        );
+        // tsumo adds menzen tsumo: 4 han 20 fu
+        assert_eq!(score(open_riichi().tsumo(), &rules), (false, None, 5200));
+        let no_deal_in = RuleSet {
+            open_riichi_deal_in_yakuman: false,
+            ..rules.clone()
+        };
+        assert_eq!(score(ron, &no_deal_in), (false, None, 3900));
+    }
+}
@@ -0,0 +1,26 @@
+//! Riichi Mahjong scoring engine.
+//!
+//! The engine takes a winning hand with its game context ([`UserInput`]) and
+//! returns the yaku, han, fu and payments ([`AgariResult`]). The iced
+//! frontend is a separate binary behind the `gui` feature.
+
+pub mod implements;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+pub use implements::types::{
+    builder::UserInputBuilder,
+    error::{AgariError, ParseHaiError, ParseHandError, ReplayError, RuleError},
+    game::{AgariType, GameContext, PlayerContext},
+    hand::{AgariHand, HandOrganization, HandStructure, Machi, Mentsu, MentsuType},
+    input::{ClosedKanInput, OpenMeldInput, UserInput},
+    rules::{RenhouValue, RuleSet},
+    scoring::{AgariResult, HandLimit},
+    tiles::{Hai, Jihai, Kaze, Sangenpai, Suhai, Suit},
+    yaku::Yaku,
+};
+pub use implements::{
+    YakuResult, calculate_agari, calculate_agari_all, calculate_score, check_all_yaku,
+    organize_hand, parse_hand,
+};
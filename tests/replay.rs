@@ -0,0 +1,211 @@
+// Game records under tests/fixtures, checked against the engine
+#![cfg(feature = "replay")]
+
+use riichi_mahjong_calculator::RuleSet;
+use riichi_mahjong_calculator::implements::replay::{
+    ExpectedScore, RecordedAgari, majsoul, mjai, tenhou, tenhou6, verify,
+};
+use riichi_mahjong_calculator::{Kaze, Yaku, parse_hand};
+
+fn fixture(name: &str) -> String {
+    let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+    std::fs::read_to_string(&path).unwrap_or_else(|err| panic!("{}: {}", path, err))
+}
+
+fn assert_all_agree(agari_list: &[RecordedAgari], rules: &RuleSet) {
+    for agari in agari_list {
+        let discrepancies = verify(agari, rules);
+        assert!(
+            discrepancies.is_empty(),
+            "{}: {:?}",
+            agari.round,
+            discrepancies
+        );
+    }
+}
+
+#[test]
+fn tenhou6_red_five_added_kan() {
+    // a plain five added to a red pon, then a red five added to a plain pon
+    let rounds = tenhou6::parse_log(&fixture("tenhou6_red_kakan.json")).unwrap();
+    let agari_list: Vec<RecordedAgari> = rounds.into_iter().flat_map(|r| r.agari).collect();
+    assert_eq!(agari_list.len(), 2);
+    assert_all_agree(&agari_list, &RuleSet::preset("tenhou").unwrap());
+}
+
+#[test]
+fn tenhou6_export_round_trip() {
+    let rules = RuleSet::preset("tenhou").unwrap();
+    let rounds = tenhou6::parse_log(&fixture("tenhou6_red_kakan.json")).unwrap();
+    let hands: Vec<_> = rounds
+        .into_iter()
+        .flat_map(|r| r.agari)
+        .map(|agari| {
+            let input = agari.input.unwrap();
+            let result = riichi_mahjong_calculator::calculate_agari(&input, &rules).unwrap();
+            (input, result)
+        })
+        .collect();
+
+    let exported = tenhou6::export_log(&hands, &rules);
+    let agari_list: Vec<RecordedAgari> = tenhou6::parse_log(&exported)
+        .unwrap()
+        .into_iter()
+        .flat_map(|r| r.agari)
+        .collect();
+    assert_eq!(agari_list.len(), hands.len());
+    assert_all_agree(&agari_list, &rules);
+}
+
+#[test]
+fn tenhou_mjlog_agrees_under_tenhou_rules() {
+    let agari_list = tenhou::parse_mjlog(&fixture("tenhou_agari.mjlog")).unwrap();
+    assert_eq!(agari_list.len(), 3);
+    assert_all_agree(&agari_list, &RuleSet::preset("tenhou").unwrap());
+}
+
+#[test]
+fn tenhou_mjlog_suuankou_tanki_is_double_under_standard_rules() {
+    // 四暗刻単騎 is a single yakuman on Tenhou
+    let agari_list = tenhou::parse_mjlog(&fixture("tenhou_agari.mjlog")).unwrap();
+    let fields: Vec<&str> = verify(&agari_list[2], &RuleSet::default())
+        .iter()
+        .map(|discrepancy| discrepancy.field)
+        .collect();
+    assert_eq!(fields, ["han", "points", "point_deltas"]);
+}
+
+#[test]
+fn majsoul_paipu_agrees_under_majsoul_rules() {
+    // riichi ron with a carried stick, dealer tsumo with a red five in a chii,
+    // a kan dora after a closed kan, a non-dealer yakuman tsumo
+    let agari_list = majsoul::parse_paipu(&fixture("majsoul_paipu.json")).unwrap();
+    let rounds: Vec<&str> = agari_list
+        .iter()
+        .map(|agari| agari.round.as_str())
+        .collect();
+    assert_eq!(
+        rounds,
+        [
+            "East 1, 1 honba",
+            "East 2, 0 honba",
+            "East 2, 1 honba",
+            "East 3, 0 honba"
+        ]
+    );
+    let sticks: Vec<u8> = agari_list
+        .iter()
+        .map(|agari| agari.input.as_ref().unwrap().game_context.kyoutaku)
+        .collect();
+    assert_eq!(sticks, [2, 0, 0, 0]);
+    assert!(
+        agari_list
+            .iter()
+            .all(|agari| agari.expected.point_deltas.is_some())
+    );
+    assert_eq!(agari_list[3].expected.han, 13);
+    assert_all_agree(&agari_list, &RuleSet::preset("majsoul").unwrap());
+}
+
+#[test]
+fn majsoul_actions_record_agrees() {
+    // newer records wrap each record in an action; user inputs are skipped
+    let agari_list = majsoul::parse_paipu(&fixture("majsoul_actions.json")).unwrap();
+    assert_eq!(agari_list.len(), 1);
+    assert_eq!(agari_list[0].round, "South 4, 2 honba");
+    assert_all_agree(&agari_list, &RuleSet::preset("majsoul").unwrap());
+}
+
+#[test]
+fn mjai_situational_yaku_follow_the_stream() {
+    let rules = RuleSet::preset("tenhou").unwrap();
+    let hora_list = mjai::parse_events(&fixture("mjai_situational.jsonl"), &rules).unwrap();
+    for hora in &hora_list {
+        assert!(hora.event_deltas.is_some(), "{}", hora.round);
+        assert_eq!(hora.discrepancies(), [], "{}", hora.round);
+    }
+
+    let situational = [
+        Yaku::DaburuRiichi,
+        Yaku::Riichi,
+        Yaku::Ippatsu,
+        Yaku::RinshanKaihou,
+        Yaku::Chankan,
+        Yaku::HaiteiRaoyue,
+        Yaku::HouteiRaoyui,
+        Yaku::Tenhou,
+        Yaku::Chiihou,
+    ];
+    let found: Vec<Vec<Yaku>> = hora_list
+        .iter()
+        .map(|hora| {
+            let yaku_list = &hora.result.as_ref().unwrap().yaku_list;
+            situational
+                .into_iter()
+                .filter(|yaku| yaku_list.contains(yaku))
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        found,
+        [
+            vec![Yaku::DaburuRiichi, Yaku::Ippatsu], // no call before the first discard
+            vec![Yaku::Riichi],                      // a pon broke ippatsu
+            vec![Yaku::RinshanKaihou],               // replacement draw after a closed kan
+            vec![Yaku::Chankan],                     // ron on an added kan
+            vec![Yaku::HouteiRaoyui],                // double ron on the last discard
+            vec![Yaku::HouteiRaoyui],
+            vec![Yaku::Tenhou],
+            vec![Yaku::Chiihou],
+        ]
+    );
+
+    // honba and sticks go to the first winner of the double ron only
+    let sticks: Vec<(u8, u8)> = hora_list[4..6]
+        .iter()
+        .map(|hora| {
+            let game = &hora.input.as_ref().unwrap().game_context;
+            (game.honba, game.kyoutaku)
+        })
+        .collect();
+    assert_eq!(sticks, [(2, 1), (0, 0)]);
+}
+
+#[test]
+fn mjai_rejects_mpsz_tiles() {
+    let stream = [
+        r#"{"type":"start_kyoku","bakaze":"E","kyoku":1,"honba":0,"kyotaku":0,"oya":0,"dora_marker":"E","tehais":[["1m"],["1m"],["1m"],["1m"]]}"#,
+        r#"{"type":"tsumo","actor":0,"pai":"1z"}"#,
+    ]
+    .join("\n");
+    let err = mjai::parse_events(&stream, &RuleSet::default()).unwrap_err();
+    assert_eq!(err.context, "line 2");
+
+    let red = stream.replace("1z", "0m");
+    assert!(mjai::parse_events(&red, &RuleSet::default()).is_err());
+    let mjai_red = stream.replace("1z", "5mr");
+    assert!(mjai::parse_events(&mjai_red, &RuleSet::default()).is_ok());
+}
+
+#[test]
+fn sanma_tsumo_points_leave_out_two_payers_honba() {
+    // 700/1300 with 30 honba: two payers add 3000 each
+    let input = parse_hand("123p456p789s11z22z 2z")
+        .unwrap()
+        .tsumo()
+        .seat(Kaze::Nan)
+        .honba(30)
+        .build();
+    let agari = RecordedAgari {
+        round: "East 1, 30 honba".to_string(),
+        winner: 1,
+        input,
+        expected: ExpectedScore {
+            han: 2,
+            fu: Some(40),
+            points: 2000,
+            point_deltas: None,
+        },
+    };
+    assert_eq!(verify(&agari, &RuleSet::preset("sanma").unwrap()), []);
+}